@@ -0,0 +1,564 @@
+// Cargo.toml dependencies needed (in addition to the ones listed in main.rs):
+// tokio = { version = "1.0", features = ["full"] } # already present, net + sync features used here
+
+//! Distributed agent execution subsystem.
+//!
+//! A SecV server can dispatch `ExecutionContext`s to remote agents over a
+//! plain TCP transport (newline-delimited JSON, using the same serde/serde_json
+//! stack the rest of the codebase relies on) and collect `ModuleResult`s back.
+//! This lets a workflow fan scans out across many hosts/networks instead of
+//! only ever running modules in-process on the local host.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use colored::*;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Mutex};
+
+use tokio_util::sync::CancellationToken;
+
+use crate::{ErrorAction, ExecutionContext, ModuleLoader, ModuleResult, SecVError, WorkflowStep};
+
+/// How many consecutive missed heartbeats before an agent is marked `Offline`.
+const MAX_MISSED_HEARTBEATS: u32 = 3;
+
+/// The agent's place in its execution lifecycle.
+///
+/// `Registered -> Idle -> Running(job_id) -> Reporting -> Idle`, with
+/// `Offline` reachable from any state once heartbeats stop arriving.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AgentState {
+    Registered,
+    Idle,
+    Running(String),
+    Reporting,
+    Offline,
+}
+
+/// Server-side view of a remote agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Agent {
+    pub id: String,
+    pub capabilities: Vec<String>,
+    pub category: String,
+    pub state: AgentState,
+    #[serde(skip, default = "Instant::now")]
+    pub last_heartbeat: Instant,
+    pub missed_heartbeats: u32,
+}
+
+impl Agent {
+    pub fn new(id: String, capabilities: Vec<String>, category: String) -> Self {
+        Self {
+            id,
+            capabilities,
+            category,
+            state: AgentState::Registered,
+            last_heartbeat: Instant::now(),
+            missed_heartbeats: 0,
+        }
+    }
+
+    /// Returns true if this agent advertises the step's required category
+    /// (an agent registered with an empty category is a generalist that
+    /// matches any) and every one of its required capabilities.
+    pub fn matches(&self, required_category: Option<&str>, required_capabilities: &[String]) -> bool {
+        let category_matches = match required_category {
+            Some(category) => self.category == category || self.category.is_empty(),
+            None => true,
+        };
+        category_matches
+            && required_capabilities
+                .iter()
+                .all(|cap| self.capabilities.contains(cap))
+    }
+}
+
+/// Messages exchanged between the server and its remote agents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AgentMessage {
+    RegisterAgent {
+        agent_id: String,
+        capabilities: Vec<String>,
+        category: String,
+    },
+    AssignJob {
+        job_id: String,
+        step: WorkflowStep,
+        context: Box<ExecutionContext>,
+    },
+    JobResult {
+        job_id: String,
+        result: ModuleResult,
+    },
+    Heartbeat {
+        agent_id: String,
+    },
+}
+
+/// Server-side registry of known agents, keyed by agent ID.
+#[derive(Default)]
+pub struct AgentRegistry {
+    agents: Mutex<HashMap<String, Agent>>,
+    /// Channel used to push job assignments to a connected agent's writer task.
+    senders: Mutex<HashMap<String, mpsc::UnboundedSender<AgentMessage>>>,
+}
+
+impl AgentRegistry {
+    pub fn new() -> Self {
+        Self {
+            agents: Mutex::new(HashMap::new()),
+            senders: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn register(
+        &self,
+        agent_id: String,
+        capabilities: Vec<String>,
+        category: String,
+        sender: mpsc::UnboundedSender<AgentMessage>,
+    ) {
+        let mut agent = Agent::new(agent_id.clone(), capabilities, category);
+        agent.state = AgentState::Idle;
+        println!(
+            "{} {}",
+            "🤝 Agent registered:".green().bold(),
+            agent_id.cyan()
+        );
+        self.agents.lock().await.insert(agent_id.clone(), agent);
+        self.senders.lock().await.insert(agent_id, sender);
+    }
+
+    pub async fn heartbeat(&self, agent_id: &str) {
+        if let Some(agent) = self.agents.lock().await.get_mut(agent_id) {
+            agent.last_heartbeat = Instant::now();
+            agent.missed_heartbeats = 0;
+            if agent.state == AgentState::Offline {
+                agent.state = AgentState::Idle;
+            }
+        }
+    }
+
+    /// Sweeps all agents, transitioning any that missed too many heartbeats to `Offline`.
+    /// Returns the job IDs of in-flight steps that need to be rescheduled.
+    pub async fn sweep_offline(&self, heartbeat_interval: Duration) -> Vec<String> {
+        let mut stale_jobs = Vec::new();
+        let mut agents = self.agents.lock().await;
+        for agent in agents.values_mut() {
+            if agent.state == AgentState::Offline {
+                continue;
+            }
+            if agent.last_heartbeat.elapsed() > heartbeat_interval {
+                agent.missed_heartbeats += 1;
+                if agent.missed_heartbeats >= MAX_MISSED_HEARTBEATS {
+                    if let AgentState::Running(job_id) = &agent.state {
+                        stale_jobs.push(job_id.clone());
+                    }
+                    agent.state = AgentState::Offline;
+                    println!(
+                        "{} {}",
+                        "⚠️  Agent went offline:".yellow().bold(),
+                        agent.id.cyan()
+                    );
+                }
+            }
+        }
+        stale_jobs
+    }
+
+    /// Picks an idle agent whose capabilities/category match the requested step,
+    /// marks it `Running(job_id)`, and returns its sender for dispatch.
+    pub async fn pick_idle_agent(
+        &self,
+        required_category: Option<&str>,
+        required_capabilities: &[String],
+        job_id: &str,
+    ) -> Option<mpsc::UnboundedSender<AgentMessage>> {
+        let mut agents = self.agents.lock().await;
+        let chosen_id = agents
+            .values()
+            .find(|a| a.state == AgentState::Idle && a.matches(required_category, required_capabilities))
+            .map(|a| a.id.clone())?;
+
+        if let Some(agent) = agents.get_mut(&chosen_id) {
+            agent.state = AgentState::Running(job_id.to_string());
+        }
+        drop(agents);
+
+        self.senders.lock().await.get(&chosen_id).cloned()
+    }
+
+    pub async fn mark_reporting(&self, agent_id: &str) {
+        if let Some(agent) = self.agents.lock().await.get_mut(agent_id) {
+            agent.state = AgentState::Reporting;
+        }
+    }
+
+    pub async fn mark_idle(&self, agent_id: &str) {
+        if let Some(agent) = self.agents.lock().await.get_mut(agent_id) {
+            agent.state = AgentState::Idle;
+        }
+    }
+}
+
+/// Pending job awaiting a `JobResult` from whichever agent it was assigned to.
+/// Keeps everything needed to reassign the same work to a different agent if
+/// its current one goes `Offline` before reporting back.
+struct PendingJob {
+    reply: tokio::sync::oneshot::Sender<ModuleResult>,
+    step: WorkflowStep,
+    context: Box<ExecutionContext>,
+    /// Remaining reschedule attempts if the assigned agent goes offline.
+    /// Seeded from `ErrorAction::Retry(n)` (at least one attempt always, so
+    /// a single offline agent doesn't fail the step outright).
+    reschedules_remaining: u32,
+}
+
+/// Coordinates job dispatch and result collection across the agent fleet.
+#[derive(Default)]
+pub struct AgentServer {
+    pub registry: Arc<AgentRegistry>,
+    pending: Mutex<HashMap<String, PendingJob>>,
+    next_job_id: Mutex<u64>,
+}
+
+impl AgentServer {
+    pub fn new() -> Self {
+        Self {
+            registry: Arc::new(AgentRegistry::new()),
+            pending: Mutex::new(HashMap::new()),
+            next_job_id: Mutex::new(0),
+        }
+    }
+
+    pub async fn bind(self: Arc<Self>, addr: &str) -> Result<(), SecVError> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(SecVError::IoError)?;
+        println!(
+            "{} {}",
+            "📡 Agent server listening on".cyan().bold(),
+            addr.white()
+        );
+
+        loop {
+            let (socket, peer) = listener.accept().await.map_err(SecVError::IoError)?;
+            println!("{} {}", "🔌 Agent connection from".blue(), peer);
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = server.handle_connection(socket).await {
+                    eprintln!("{} {}", "Agent connection error:".red(), e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection(self: Arc<Self>, socket: TcpStream) -> Result<(), SecVError> {
+        let (reader, mut writer) = socket.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let (tx, mut rx) = mpsc::unbounded_channel::<AgentMessage>();
+        let mut registered_id: Option<String> = None;
+
+        let writer_task = tokio::spawn(async move {
+            while let Some(msg) = rx.recv().await {
+                if let Ok(mut line) = serde_json::to_string(&msg) {
+                    line.push('\n');
+                    if writer.write_all(line.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        while let Some(line) = lines.next_line().await.map_err(SecVError::IoError)? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let message: AgentMessage = match serde_json::from_str(&line) {
+                Ok(m) => m,
+                Err(e) => {
+                    eprintln!("{} {}", "Malformed agent message:".red(), e);
+                    continue;
+                }
+            };
+
+            match message {
+                AgentMessage::RegisterAgent {
+                    agent_id,
+                    capabilities,
+                    category,
+                } => {
+                    self.registry
+                        .register(agent_id.clone(), capabilities, category, tx.clone())
+                        .await;
+                    registered_id = Some(agent_id);
+                }
+                AgentMessage::Heartbeat { agent_id } => {
+                    self.registry.heartbeat(&agent_id).await;
+                }
+                AgentMessage::JobResult { job_id, result } => {
+                    if let Some(agent_id) = &registered_id {
+                        self.registry.mark_reporting(agent_id).await;
+                    }
+                    if let Some(pending) = self.pending.lock().await.remove(&job_id) {
+                        let _ = pending.reply.send(result);
+                    }
+                    if let Some(agent_id) = &registered_id {
+                        self.registry.mark_idle(agent_id).await;
+                    }
+                }
+                AgentMessage::AssignJob { .. } => {
+                    // Servers only ever send AssignJob; agents don't originate it.
+                }
+            }
+        }
+
+        writer_task.abort();
+        Ok(())
+    }
+
+    async fn next_job_id(&self) -> String {
+        let mut counter = self.next_job_id.lock().await;
+        *counter += 1;
+        format!("job-{}", *counter)
+    }
+
+    /// Dispatches a workflow step to an idle, capability-matching agent and
+    /// awaits its `ModuleResult`, reconciling it into `context.results` exactly
+    /// as the local execution path does. Uses `step.required_category`/
+    /// `step.required_capabilities` (not the module name) to pick the agent.
+    pub async fn dispatch_step(
+        &self,
+        step: &WorkflowStep,
+        context: &ExecutionContext,
+        timeout: Duration,
+    ) -> Result<ModuleResult, SecVError> {
+        let job_id = self.next_job_id().await;
+        let sender = self
+            .registry
+            .pick_idle_agent(step.required_category.as_deref(), &step.required_capabilities, &job_id)
+            .await
+            .ok_or_else(|| {
+                SecVError::ExecutionFailed(format!(
+                    "No idle agent available for module '{}'",
+                    step.module
+                ))
+            })?;
+
+        let reschedules_remaining = match step.on_error {
+            ErrorAction::Retry(n) => n.max(1),
+            _ => 1,
+        };
+        let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+        self.pending.lock().await.insert(job_id.clone(), PendingJob {
+            reply: reply_tx,
+            step: step.clone(),
+            context: Box::new(context.clone()),
+            reschedules_remaining,
+        });
+
+        sender
+            .send(AgentMessage::AssignJob {
+                job_id: job_id.clone(),
+                step: step.clone(),
+                context: Box::new(context.clone()),
+            })
+            .map_err(|_| SecVError::ExecutionFailed("Agent channel closed".to_string()))?;
+
+        match tokio::time::timeout(timeout, reply_rx).await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(_)) => Err(SecVError::ExecutionFailed(
+                "Agent disconnected before reporting a result".to_string(),
+            )),
+            Err(_) => {
+                self.pending.lock().await.remove(&job_id);
+                Err(SecVError::ExecutionFailed(format!(
+                    "Job '{}' timed out waiting for agent result",
+                    job_id
+                )))
+            }
+        }
+    }
+
+    /// Spawns a background task that periodically sweeps `registry` for
+    /// agents that have missed too many heartbeats and reschedules their
+    /// in-flight jobs onto a different idle agent, honoring each step's
+    /// `reschedules_remaining` budget (seeded from `ErrorAction::Retry`).
+    /// A job that runs out of agents or reschedule budget is failed by
+    /// dropping its reply sender, which surfaces to the original caller as
+    /// "Agent disconnected before reporting a result".
+    pub fn spawn_heartbeat_sweep(self: Arc<Self>, heartbeat_interval: Duration) {
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(heartbeat_interval);
+            loop {
+                ticks.tick().await;
+                let stale_jobs = self.registry.sweep_offline(heartbeat_interval).await;
+                for job_id in stale_jobs {
+                    self.reschedule_job(job_id).await;
+                }
+            }
+        });
+    }
+
+    /// Reassigns `job_id`'s step to a newly-picked idle agent, reusing the
+    /// original reply channel so the caller still awaiting in `dispatch_step`
+    /// gets the eventual result (or sees the job fail if rescheduling isn't
+    /// possible).
+    async fn reschedule_job(&self, job_id: String) {
+        let mut pending_jobs = self.pending.lock().await;
+        let job = pending_jobs.remove(&job_id);
+        drop(pending_jobs);
+
+        let mut job = match job {
+            Some(job) => job,
+            None => return,
+        };
+
+        if job.reschedules_remaining == 0 {
+            println!(
+                "{} {}",
+                "⛔ Job exhausted its reschedule budget, failing:".red().bold(),
+                job_id.cyan()
+            );
+            return; // dropping `job.reply` fails the original caller
+        }
+        job.reschedules_remaining -= 1;
+
+        let new_job_id = self.next_job_id().await;
+        match self
+            .registry
+            .pick_idle_agent(job.step.required_category.as_deref(), &job.step.required_capabilities, &new_job_id)
+            .await
+        {
+            Some(sender) => {
+                println!(
+                    "{} {} {} {}",
+                    "🔁 Rescheduling job".magenta().bold(),
+                    job_id.cyan(),
+                    "as".magenta().bold(),
+                    new_job_id.cyan()
+                );
+                let send_result = sender.send(AgentMessage::AssignJob {
+                    job_id: new_job_id.clone(),
+                    step: job.step.clone(),
+                    context: job.context.clone(),
+                });
+                if send_result.is_ok() {
+                    self.pending.lock().await.insert(new_job_id, job);
+                }
+                // else: the channel closed between pick and send; drop `job`, failing the caller.
+            }
+            None => {
+                // No idle agent right now; put the job back under its original
+                // ID so the next sweep tick can try again.
+                self.pending.lock().await.insert(job_id, job);
+            }
+        }
+    }
+}
+
+/// Runs as a remote agent (`secv agent`): connects to a `secv serve-agents`
+/// coordinator, registers with `category`/`capabilities`, sends a `Heartbeat`
+/// every `heartbeat_interval`, and executes any `AssignJob` it receives
+/// against `module_loader`, reporting the `ModuleResult` back as `JobResult`.
+/// Runs until the connection drops or the process is killed.
+pub async fn run_client(
+    server_addr: &str,
+    agent_id: String,
+    category: String,
+    capabilities: Vec<String>,
+    module_loader: Arc<ModuleLoader>,
+    heartbeat_interval: Duration,
+) -> Result<(), SecVError> {
+    let stream = TcpStream::connect(server_addr).await.map_err(SecVError::IoError)?;
+    println!(
+        "{} {} {}",
+        "📡 Connected to coordinator".cyan().bold(),
+        server_addr.white(),
+        format!("as '{}'", agent_id).cyan()
+    );
+
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    let register = AgentMessage::RegisterAgent {
+        agent_id: agent_id.clone(),
+        capabilities,
+        category,
+    };
+    writer.write_all(format!("{}\n", serde_json::to_string(&register)?).as_bytes()).await.map_err(SecVError::IoError)?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<AgentMessage>();
+    let writer_task = tokio::spawn(async move {
+        while let Some(msg) = rx.recv().await {
+            if let Ok(mut line) = serde_json::to_string(&msg) {
+                line.push('\n');
+                if writer.write_all(line.as_bytes()).await.is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let heartbeat_id = agent_id.clone();
+    let heartbeat_tx = tx.clone();
+    let heartbeat_task = tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(heartbeat_interval);
+        loop {
+            ticks.tick().await;
+            if heartbeat_tx.send(AgentMessage::Heartbeat { agent_id: heartbeat_id.clone() }).is_err() {
+                break;
+            }
+        }
+    });
+
+    while let Some(line) = lines.next_line().await.map_err(SecVError::IoError)? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message: AgentMessage = match serde_json::from_str(&line) {
+            Ok(m) => m,
+            Err(e) => {
+                eprintln!("{} {}", "Malformed coordinator message:".red(), e);
+                continue;
+            }
+        };
+
+        if let AgentMessage::AssignJob { job_id, step, context } = message {
+            println!("{} {} ({})", "⚙️  Assigned job".yellow().bold(), job_id.cyan(), step.name);
+            let result = run_assigned_step(&module_loader, &step.module, *context).await;
+            let _ = tx.send(AgentMessage::JobResult { job_id, result });
+        }
+    }
+
+    heartbeat_task.abort();
+    writer_task.abort();
+    Ok(())
+}
+
+/// Executes a job assigned by the coordinator against this agent's local
+/// `module_loader`, turning a missing module or execution error into a
+/// failed `ModuleResult` rather than dropping the job silently.
+async fn run_assigned_step(module_loader: &ModuleLoader, module_name: &str, context: ExecutionContext) -> ModuleResult {
+    let failed = |message: String| ModuleResult {
+        success: false,
+        data: serde_json::Value::Null,
+        errors: vec![message],
+        warnings: vec![],
+        execution_time_ms: 0,
+        artifacts: vec![],
+    };
+
+    match module_loader.get_module(module_name) {
+        Some(module) => match module.execute(context, CancellationToken::new()).await {
+            Ok(result) => result,
+            Err(e) => failed(format!("Execution failed on agent: {}", e)),
+        },
+        None => failed(format!("Module '{}' not loaded on this agent", module_name)),
+    }
+}
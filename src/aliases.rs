@@ -0,0 +1,57 @@
+//! User-defined module aliases and canned parameter sets.
+//!
+//! Maps a short name (e.g. `portscan`) to a fully-qualified module name plus
+//! a parameter set a team already agreed on, so `secv execute -m portscan`
+//! can stand in for `secv execute -m network-scanner -p '{"ports": "1-1000"}'`.
+//! Loaded from `aliases.json`, the same JSON-first convention the policy
+//! file in `permissions.rs` settled on.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::SecVError;
+
+/// One alias entry: the module it resolves to, and parameters merged in
+/// ahead of whatever the caller passes on the command line.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AliasEntry {
+    pub module: String,
+    #[serde(default)]
+    pub params: HashMap<String, serde_json::Value>,
+}
+
+/// Short name -> alias entry, keyed the way it appears after `-m`/`--module`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AliasTable(HashMap<String, AliasEntry>);
+
+impl AliasTable {
+    /// Loads `aliases.json` from the current directory if present; aliases
+    /// are an opt-in convenience, so a missing file just means none defined.
+    pub async fn load_default() -> Result<Self, SecVError> {
+        let path = Path::new("aliases.json");
+        if !tokio::fs::try_exists(path).await.unwrap_or(false) {
+            return Ok(Self::default());
+        }
+        Self::load(path).await
+    }
+
+    pub async fn load(path: &Path) -> Result<Self, SecVError> {
+        let content = tokio::fs::read_to_string(path).await?;
+        let table: HashMap<String, AliasEntry> = serde_json::from_str(&content)?;
+        Ok(Self(table))
+    }
+
+    pub fn resolve(&self, name: &str) -> Option<&AliasEntry> {
+        self.0.get(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
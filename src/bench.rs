@@ -0,0 +1,211 @@
+//! Latency benchmarking and environment capture (`secv bench`).
+//!
+//! Runs a module or workflow repeatedly against a target, discards
+//! `warmup` iterations, and reports latency statistics derived from the
+//! same `execution_time_ms` every `ModuleResult` already carries (a
+//! workflow has no single such field, so its per-iteration sample is the
+//! wall-clock time around the whole `execute_workflow` call instead). An
+//! environment snapshot travels with the numbers so a report stays
+//! meaningful when compared across machines or saved for later.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::permissions::PermissionPolicy;
+use crate::{ExecutionContext, SecVError, SecVModule, ShuffleMode, WorkflowDefinition, WorkflowEngine};
+
+/// Static build version, matching the one `SecVCli` reports via `--version`.
+const SECV_VERSION: &str = "2.0.0";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Environment {
+    pub os: String,
+    pub arch: String,
+    pub hostname: String,
+    pub cpu_model: String,
+    pub cpu_cores: usize,
+    pub total_memory_kb: Option<u64>,
+    pub secv_version: String,
+    pub dependency_versions: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LatencyStats {
+    pub samples: usize,
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub mean_ms: f64,
+    pub median_ms: f64,
+    pub p95_ms: f64,
+    pub stddev_ms: f64,
+}
+
+impl LatencyStats {
+    pub fn from_samples(mut samples: Vec<f64>) -> Result<Self, SecVError> {
+        if samples.is_empty() {
+            return Err(SecVError::ValidationFailed(
+                "No samples to compute statistics from (increase --iterations)".to_string(),
+            ));
+        }
+
+        samples.sort_by(|a, b| a.partial_cmp(b).expect("execution_time_ms samples are never NaN"));
+        let n = samples.len();
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Ok(Self {
+            samples: n,
+            min_ms: samples[0],
+            max_ms: samples[n - 1],
+            mean_ms: mean,
+            median_ms: percentile(&samples, 0.5),
+            p95_ms: percentile(&samples, 0.95),
+            stddev_ms: variance.sqrt(),
+        })
+    }
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub subject: String,
+    pub target: String,
+    pub iterations: u32,
+    pub warmup: u32,
+    pub stats: LatencyStats,
+    pub environment: Environment,
+}
+
+/// Runs `module` `iterations + warmup` times, keeping only the samples
+/// after the warmup period.
+pub async fn run_module_bench(
+    module: Arc<dyn SecVModule>,
+    permission_policy: &PermissionPolicy,
+    target: String,
+    parameters: HashMap<String, serde_json::Value>,
+    iterations: u32,
+    warmup: u32,
+) -> Result<Vec<f64>, SecVError> {
+    permission_policy.check(module.metadata()).await?;
+
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for run in 0..(iterations + warmup) {
+        let context = ExecutionContext {
+            target: target.clone(),
+            parameters: parameters.clone(),
+            results: HashMap::new(),
+            metadata: HashMap::new(),
+        };
+        let label = if run < warmup { " (warmup)" } else { "" };
+        println!("  run {}/{}{}", run + 1, iterations + warmup, label);
+
+        let result = module.execute(context, CancellationToken::new()).await?;
+        if run >= warmup {
+            samples.push(result.execution_time_ms as f64);
+        }
+    }
+    Ok(samples)
+}
+
+/// Runs `workflow` `iterations + warmup` times, sampling the wall-clock
+/// time of each full `execute_workflow` call.
+pub async fn run_workflow_bench(
+    workflow_engine: &WorkflowEngine,
+    workflow: &WorkflowDefinition,
+    target: String,
+    iterations: u32,
+    warmup: u32,
+) -> Result<Vec<f64>, SecVError> {
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for run in 0..(iterations + warmup) {
+        let label = if run < warmup { " (warmup)" } else { "" };
+        println!("  run {}/{}{}", run + 1, iterations + warmup, label);
+
+        let started = std::time::Instant::now();
+        workflow_engine
+            .execute_workflow(workflow.clone(), target.clone(), CancellationToken::new(), None, ShuffleMode::Off)
+            .await
+            .map_err(|e| SecVError::WorkflowError(e.to_string()))?;
+
+        if run >= warmup {
+            samples.push(started.elapsed().as_secs_f64() * 1000.0);
+        }
+    }
+    Ok(samples)
+}
+
+/// Snapshots the host this benchmark ran on, plus the resolved version of
+/// each of `dependencies` (via `<dep> --version`), so a saved report stays
+/// meaningful when read on a different machine later.
+pub async fn capture_environment(dependencies: &[String]) -> Environment {
+    let hostname = run_capture("hostname", &[]).await.unwrap_or_else(|| "unknown".to_string());
+    let cpu_cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+
+    let mut dependency_versions = HashMap::new();
+    for dep in dependencies {
+        let version = run_capture(dep, &["--version"]).await.unwrap_or_else(|| "unavailable".to_string());
+        dependency_versions.insert(dep.clone(), version);
+    }
+
+    Environment {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        hostname,
+        cpu_model: read_cpu_model().await,
+        cpu_cores,
+        total_memory_kb: read_total_memory_kb().await,
+        secv_version: SECV_VERSION.to_string(),
+        dependency_versions,
+    }
+}
+
+/// Flags a regression when `current`'s mean latency is more than
+/// `threshold_pct` worse than `previous`'s.
+pub fn diff_against(previous: &BenchReport, current: &LatencyStats, threshold_pct: f64) -> Option<String> {
+    let delta_pct = (current.mean_ms - previous.stats.mean_ms) / previous.stats.mean_ms * 100.0;
+    if delta_pct > threshold_pct {
+        Some(format!(
+            "mean latency regressed {:.1}% ({:.2}ms -> {:.2}ms, threshold {:.1}%)",
+            delta_pct, previous.stats.mean_ms, current.mean_ms, threshold_pct
+        ))
+    } else {
+        None
+    }
+}
+
+async fn run_capture(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = tokio::process::Command::new(cmd).args(args).output().await.ok()?;
+    let text = if !output.stdout.is_empty() {
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    } else {
+        String::from_utf8_lossy(&output.stderr).into_owned()
+    };
+    text.lines().next().map(|line| line.trim().to_string())
+}
+
+/// Best-effort; `/proc/cpuinfo` only exists on Linux, so other platforms
+/// just report "unknown" rather than failing the whole benchmark.
+async fn read_cpu_model() -> String {
+    match tokio::fs::read_to_string("/proc/cpuinfo").await {
+        Ok(content) => content
+            .lines()
+            .find(|line| line.starts_with("model name"))
+            .and_then(|line| line.split(':').nth(1))
+            .map(|model| model.trim().to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+        Err(_) => "unknown".to_string(),
+    }
+}
+
+async fn read_total_memory_kb() -> Option<u64> {
+    let content = tokio::fs::read_to_string("/proc/meminfo").await.ok()?;
+    let line = content.lines().find(|line| line.starts_with("MemTotal:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
@@ -0,0 +1,186 @@
+//! Long-running service mode (`secv daemon`).
+//!
+//! Keeps a `ModuleLoader` warm in memory, re-runs `health_check()` across
+//! every loaded module on a timer, serves the same HTTP control plane
+//! `secv serve` does, and treats a `workflows/` directory as a drop queue —
+//! any workflow file that shows up there is picked up and run once. Process
+//! supervisors (systemd) are told when startup finished, that the process
+//! is still alive, and when it's shutting down, via the `systemd` cargo
+//! feature; without that feature every notification is a no-op so the
+//! daemon still runs fine under a plain process manager or in the
+//! foreground.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use colored::*;
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+use crate::permissions::PermissionPolicy;
+use crate::{ModuleLoader, ShuffleMode, WorkflowEngine};
+
+#[cfg(feature = "systemd")]
+mod notify {
+    use sd_notify::NotifyState;
+
+    pub fn ready() {
+        let _ = sd_notify::notify(false, &[NotifyState::Ready]);
+    }
+
+    pub fn watchdog() {
+        let _ = sd_notify::notify(false, &[NotifyState::Watchdog]);
+    }
+
+    pub fn stopping() {
+        let _ = sd_notify::notify(false, &[NotifyState::Stopping]);
+    }
+}
+
+#[cfg(not(feature = "systemd"))]
+mod notify {
+    pub fn ready() {}
+    pub fn watchdog() {}
+    pub fn stopping() {}
+}
+
+/// Result of the most recent `health_check()` sweep for one module.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleHealth {
+    pub healthy: bool,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Rolling health status the daemon keeps up to date, shared with whatever
+/// exports it (currently just printed; a future `/health` route on
+/// `server.rs` would read from the same board).
+pub type HealthBoard = Arc<Mutex<HashMap<String, ModuleHealth>>>;
+
+/// Runs the daemon event loop until the process receives SIGINT/Ctrl+C.
+pub async fn run(
+    module_loader: Arc<ModuleLoader>,
+    permission_policy: Arc<PermissionPolicy>,
+    bind: String,
+    token: Option<String>,
+    health_interval: Duration,
+    workflows_dir: PathBuf,
+) -> anyhow::Result<()> {
+    let health: HealthBoard = Arc::new(Mutex::new(HashMap::new()));
+    sweep_health(&module_loader, &health).await;
+
+    println!(
+        "{}",
+        format!(
+            "🟢 Daemon ready — {} module(s) tracked, health swept every {}s",
+            module_loader.module_names().len(),
+            health_interval.as_secs()
+        )
+        .green()
+        .bold()
+    );
+    notify::ready();
+
+    let server_loader = module_loader.clone();
+    let server_policy = permission_policy.clone();
+    tokio::spawn(async move {
+        if let Err(e) = crate::server::serve(&bind, token, server_loader, server_policy, None).await {
+            eprintln!("{}", format!("Control-plane server exited: {}", e).red());
+        }
+    });
+
+    let workflow_engine =
+        WorkflowEngine::new(module_loader.clone()).with_permission_policy(permission_policy.clone());
+    let mut queued: HashSet<PathBuf> = HashSet::new();
+    let mut ticks = tokio::time::interval(health_interval);
+    ticks.tick().await; // the sweep above already covers the first tick
+
+    loop {
+        tokio::select! {
+            _ = ticks.tick() => {
+                sweep_health(&module_loader, &health).await;
+                notify::watchdog();
+                drain_workflow_queue(&workflow_engine, &workflows_dir, &mut queued).await;
+            }
+            _ = tokio::signal::ctrl_c() => {
+                notify::stopping();
+                println!("\n{}", "🛑 Daemon stopping".yellow().bold());
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn sweep_health(module_loader: &ModuleLoader, health: &HealthBoard) {
+    let mut board = health.lock().await;
+    for name in module_loader.module_names() {
+        if let Some(module) = module_loader.get_module(&name) {
+            let healthy = module.health_check().await.unwrap_or(false);
+            board.insert(name, ModuleHealth { healthy, checked_at: chrono::Utc::now() });
+        }
+    }
+}
+
+/// Scans `workflows_dir` for files not already in `queued` and runs each
+/// exactly once. The workflow's `global_settings.target` supplies the
+/// target, since there's no human on hand to pass `--target`.
+async fn drain_workflow_queue(
+    workflow_engine: &WorkflowEngine,
+    workflows_dir: &Path,
+    queued: &mut HashSet<PathBuf>,
+) {
+    let mut entries = match tokio::fs::read_dir(workflows_dir).await {
+        Ok(entries) => entries,
+        Err(_) => return, // directory not created yet is not an error worth logging every tick
+    };
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        let is_workflow = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| matches!(ext, "yaml" | "yml" | "json"))
+            .unwrap_or(false);
+        if !is_workflow || queued.contains(&path) {
+            continue;
+        }
+        queued.insert(path.clone());
+
+        println!("{}", format!("📥 Picked up queued workflow {}", path.display()).magenta());
+        let workflow = match workflow_engine.load_workflow(&path).await {
+            Ok(workflow) => workflow,
+            Err(e) => {
+                eprintln!("{}", format!("Failed to load queued workflow {}: {}", path.display(), e).red());
+                continue;
+            }
+        };
+
+        let target = workflow
+            .global_settings
+            .get("target")
+            .and_then(|value| value.as_str())
+            .unwrap_or_default()
+            .to_string();
+        if target.is_empty() {
+            eprintln!(
+                "{}",
+                format!(
+                    "Queued workflow {} has no global_settings.target; running with an empty target",
+                    path.display()
+                )
+                .yellow()
+            );
+        }
+
+        let result = workflow_engine
+            .execute_workflow(workflow, target, CancellationToken::new(), None, ShuffleMode::Off)
+            .await;
+        if let Err(e) = result {
+            eprintln!("{}", format!("Queued workflow {} failed: {}", path.display(), e).red());
+        }
+    }
+}
@@ -11,6 +11,15 @@
 // colored = "2.0"
 // dialoguer = "0.11"
 // libloading = "0.8"
+// tokio-util = "0.7"
+// notify = "6.0"
+// rand = { version = "0.8", features = ["small_rng"] }
+// regex = "1.0"
+// chrono = { version = "0.4", features = ["clock", "serde"] }
+// sd-notify = { version = "0.4", optional = true } # only pulled in by the "systemd" feature
+//
+// [features]
+// systemd = ["dep:sd-notify"]
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -22,6 +31,27 @@ use colored::*;
 use dialoguer::{Select, Input, Confirm};
 use serde::{Deserialize, Serialize};
 use tokio::fs;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use tokio_util::sync::CancellationToken;
+
+mod agent;
+mod aliases;
+mod bench;
+mod daemon;
+mod native;
+mod permissions;
+mod report;
+mod server;
+mod test;
+mod watch;
+mod wizard;
+use agent::AgentServer;
+use aliases::AliasTable;
+use native::NativeModule;
+use permissions::PermissionPolicy;
+use watch::{print_diff_summary, wait_for_change};
 
 /// SecV - Next Generation Cybersecurity Orchestration Platform
 /// 
@@ -35,10 +65,42 @@ use tokio::fs;
 struct SecVCli {
     #[command(subcommand)]
     command: Option<Commands>,
-    
+
     /// Initialize directory structure
     #[arg(long)]
     init: bool,
+
+    /// Allow a capability without prompting (e.g. --allow-net, --allow-process)
+    #[arg(long = "allow-net", global = true)]
+    allow_net: bool,
+    #[arg(long = "allow-process", global = true)]
+    allow_process: bool,
+    #[arg(long = "allow-fs-read", global = true)]
+    allow_fs_read: bool,
+    #[arg(long = "allow-fs-write", global = true)]
+    allow_fs_write: bool,
+    #[arg(long = "allow-raw-socket", global = true)]
+    allow_raw_socket: bool,
+
+    /// Deny a capability outright, even if a module requests it
+    #[arg(long = "deny-net", global = true)]
+    deny_net: bool,
+    #[arg(long = "deny-process", global = true)]
+    deny_process: bool,
+    #[arg(long = "deny-fs-read", global = true)]
+    deny_fs_read: bool,
+    #[arg(long = "deny-fs-write", global = true)]
+    deny_fs_write: bool,
+    #[arg(long = "deny-raw-socket", global = true)]
+    deny_raw_socket: bool,
+
+    /// Load an allow/deny policy file (JSON: {"allow": [...], "deny": [...]})
+    #[arg(long = "policy-file", global = true)]
+    policy_file: Option<PathBuf>,
+
+    /// Never prompt for ungranted capabilities; hard-deny them instead
+    #[arg(long = "no-prompt", global = true)]
+    no_prompt: bool,
 }
 
 #[derive(Subcommand)]
@@ -54,6 +116,21 @@ enum Commands {
         /// Additional parameters in JSON format
         #[arg(short, long)]
         params: Option<String>,
+        /// Re-run whenever the module's source files change
+        #[arg(long)]
+        watch: bool,
+        /// Write a machine-readable report in this format ('junit' or 'sarif')
+        #[arg(long)]
+        report: Option<String>,
+        /// Path to write the --report output to
+        #[arg(long = "report-out")]
+        report_out: Option<PathBuf>,
+        /// Retry this many times on a transient execution failure
+        #[arg(long, default_value_t = 0)]
+        retries: u32,
+        /// Initial delay (ms) between retries; doubles each attempt up to 30s
+        #[arg(long = "retry-backoff", default_value_t = 500)]
+        retry_backoff: u64,
     },
     /// Run a workflow from file
     Workflow {
@@ -63,6 +140,32 @@ enum Commands {
         /// Primary target
         #[arg(short, long)]
         target: String,
+        /// Re-run whenever the workflow file or any loaded module.json changes
+        #[arg(long)]
+        watch: bool,
+        /// Write a machine-readable report in this format ('junit' or 'sarif')
+        #[arg(long)]
+        report: Option<String>,
+        /// Path to write the --report output to
+        #[arg(long = "report-out")]
+        report_out: Option<PathBuf>,
+        /// Run only steps whose name or module matches this glob/substring
+        #[arg(long)]
+        filter: Option<String>,
+        /// Randomize the order of independent steps (pass --seed to reproduce a run)
+        #[arg(long)]
+        shuffle: bool,
+        /// Seed for --shuffle; omit to get a random seed (printed so the run can be replayed)
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Address to listen on for remote agents; steps whose module isn't
+        /// loaded locally are dispatched to whichever agent registers
+        /// (see `secv serve-agents` to run a standalone coordinator instead)
+        #[arg(long = "agent-bind")]
+        agent_bind: Option<String>,
+        /// Seconds between heartbeat sweeps once --agent-bind is set
+        #[arg(long = "heartbeat-interval", default_value_t = 10)]
+        heartbeat_interval: u64,
     },
     /// List available modules
     List {
@@ -75,8 +178,118 @@ enum Commands {
         /// Module name
         module: String,
     },
+    /// Run a module's `*.case.json` regression fixtures (CI-friendly, exits non-zero on failure)
+    Test {
+        /// Run only this module's fixtures; omit to run every loaded module's
+        #[arg(short, long)]
+        module: Option<String>,
+    },
+    /// Measure and report module/workflow latency, with an environment snapshot for reproducibility
+    Bench {
+        /// Module to benchmark (mutually exclusive with --workflow)
+        #[arg(short, long)]
+        module: Option<String>,
+        /// Workflow file to benchmark (mutually exclusive with --module)
+        #[arg(short, long)]
+        workflow: Option<PathBuf>,
+        /// Primary target
+        #[arg(short, long)]
+        target: String,
+        /// Additional parameters in JSON format (module mode only)
+        #[arg(short, long)]
+        params: Option<String>,
+        /// Timed iterations to run after warmup
+        #[arg(short, long, default_value_t = 10)]
+        iterations: u32,
+        /// Untimed iterations to run and discard first
+        #[arg(long, default_value_t = 2)]
+        warmup: u32,
+        /// Write the full report (stats + environment) as JSON to this path
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// A previously saved report to diff this run's mean latency against
+        #[arg(long)]
+        compare: Option<PathBuf>,
+        /// Percentage mean-latency increase over --compare that counts as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Start an HTTP control-plane server exposing modules/workflows as a JSON API
+    Serve {
+        /// Address to bind to
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        bind: String,
+        /// Bearer token required on every request; omit to run unauthenticated
+        #[arg(long)]
+        token: Option<String>,
+    },
+    /// Run as a persistent service: control-plane server + health sweeps + a workflow drop queue
+    Daemon {
+        /// Address the embedded control-plane server binds to
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        bind: String,
+        /// Bearer token required on every control-plane request; omit to run unauthenticated
+        #[arg(long)]
+        token: Option<String>,
+        /// Seconds between health_check() sweeps and workflow queue checks
+        #[arg(long, default_value_t = 30)]
+        health_interval: u64,
+        /// Directory watched for workflow files to run once and mark processed
+        #[arg(long, default_value = "workflows")]
+        workflows_dir: PathBuf,
+    },
+    /// Run a standalone agent coordinator: remote agents connect and
+    /// register, and the HTTP control plane (same routes as `secv serve`)
+    /// dispatches any `POST /workflow` step whose module isn't loaded
+    /// locally out to one of them.
+    ServeAgents {
+        /// Address remote agents connect to
+        #[arg(long = "agent-bind", default_value = "0.0.0.0:9090")]
+        agent_bind: String,
+        /// Address the HTTP control plane binds to
+        #[arg(long = "control-bind", default_value = "127.0.0.1:8080")]
+        control_bind: String,
+        /// Bearer token required on every control-plane request; omit to run unauthenticated
+        #[arg(long)]
+        token: Option<String>,
+        /// Seconds between heartbeat sweeps
+        #[arg(long = "heartbeat-interval", default_value_t = 10)]
+        heartbeat_interval: u64,
+    },
+    /// Connect to a `secv serve-agents` coordinator as a remote agent and
+    /// execute whatever jobs it assigns using this host's locally loaded modules
+    Agent {
+        /// Coordinator address to connect to (its --agent-bind)
+        #[arg(long)]
+        server: String,
+        /// Unique ID this agent registers under; omit to generate one
+        #[arg(long)]
+        id: Option<String>,
+        /// Category this agent advertises (matched against a step's required_category)
+        #[arg(long, default_value = "")]
+        category: String,
+        /// Capability tags this agent advertises, comma-separated
+        #[arg(long, value_delimiter = ',')]
+        capabilities: Vec<String>,
+        /// Seconds between heartbeats sent to the coordinator
+        #[arg(long = "heartbeat-interval", default_value_t = 5)]
+        heartbeat_interval: u64,
+    },
     /// Start interactive mode
     Interactive,
+    /// Scaffold a new module or workflow with an interactive wizard
+    New {
+        #[command(subcommand)]
+        target: NewTarget,
+    },
+}
+
+#[derive(Subcommand)]
+enum NewTarget {
+    /// Walk through ModuleMetadata (name, category, inputs, outputs, ...) and write module.json
+    Module,
+    /// Assemble a multi-step workflow from already-discovered modules
+    Workflow,
 }
 
 /// Core module metadata structure with enhanced validation
@@ -92,6 +305,11 @@ pub struct ModuleMetadata {
     pub outputs: HashMap<String, OutputSpec>,
     pub capabilities: Vec<String>,
     pub risk_level: RiskLevel,
+    /// Path (relative to the module's directory) to a compiled shared
+    /// library implementing the `secv` plugin ABI. When absent, the module
+    /// is loaded as a `PlaceholderModule` instead.
+    #[serde(default)]
+    pub entry_point: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -155,7 +373,10 @@ pub enum SecVError {
     
     #[error("Workflow error: {0}")]
     WorkflowError(String),
-    
+
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
     
@@ -176,8 +397,11 @@ pub trait SecVModule: Send + Sync {
     /// Validates input parameters against the module's specification
     fn validate_inputs(&self, inputs: &HashMap<String, serde_json::Value>) -> Result<(), SecVError>;
     
-    /// Main execution method - this is where the actual work happens
-    async fn execute(&self, context: ExecutionContext) -> Result<ModuleResult, SecVError>;
+    /// Main execution method - this is where the actual work happens.
+    ///
+    /// `cancel` is cooperatively checked so callers (e.g. `--watch` mode) can
+    /// abort an in-flight execution when a fresh run has been triggered.
+    async fn execute(&self, context: ExecutionContext, cancel: CancellationToken) -> Result<ModuleResult, SecVError>;
     
     /// Optional cleanup method called after execution
     async fn cleanup(&self) -> Result<(), SecVError> {
@@ -193,17 +417,28 @@ pub trait SecVModule: Send + Sync {
 /// Advanced module loader with dynamic loading capabilities
 pub struct ModuleLoader {
     modules: HashMap<String, Arc<dyn SecVModule>>,
+    module_dirs: HashMap<String, PathBuf>,
     tools_directory: PathBuf,
+    aliases: AliasTable,
 }
 
 impl ModuleLoader {
     pub fn new(tools_directory: impl Into<PathBuf>) -> Self {
         Self {
             modules: HashMap::new(),
+            module_dirs: HashMap::new(),
             tools_directory: tools_directory.into(),
+            aliases: AliasTable::default(),
         }
     }
-    
+
+    /// Loads `aliases.json` (if present) so `get_module`/`alias_params` can
+    /// resolve short names to a full module name plus canned parameters.
+    pub async fn load_aliases(&mut self) -> Result<usize> {
+        self.aliases = AliasTable::load_default().await?;
+        Ok(self.aliases.len())
+    }
+
     /// Discovers and loads all modules from the tools directory
     pub async fn discover_modules(&mut self) -> Result<usize> {
         println!("{}", "🔍 Discovering modules...".cyan().bold());
@@ -241,22 +476,67 @@ impl ModuleLoader {
         
         let metadata: ModuleMetadata = serde_json::from_str(&metadata_content)
             .context("Failed to parse module.json")?;
-        
-        // In a real implementation, this would use dynamic loading
-        // For now, we'll create a placeholder module
-        let module = Arc::new(PlaceholderModule::new(metadata));
-        
-        println!("  {} {}", "📦".blue(), 
-                format!("Loaded: {} v{}", module.metadata().name, module.metadata().version).cyan());
-        
+
+        let module: Arc<dyn SecVModule> = match &metadata.entry_point {
+            Some(entry_point) => {
+                let native = unsafe { NativeModule::load(path, entry_point, metadata.clone()) }
+                    .context("Failed to load native module")?;
+                Arc::new(native)
+            }
+            None => Arc::new(PlaceholderModule::new(metadata)),
+        };
+
+        let kind = if module.metadata().entry_point.is_some() { "native" } else { "placeholder" };
+        println!("  {} {}", "📦".blue(),
+                format!("Loaded: {} v{} ({})", module.metadata().name, module.metadata().version, kind).cyan());
+
+        self.module_dirs.insert(module.metadata().name.clone(), path.to_path_buf());
         self.modules.insert(module.metadata().name.clone(), module);
-        
+
         Ok(())
     }
+
+    /// Directory a loaded module was discovered in, e.g. for finding the
+    /// `*.case.json` fixtures `secv test` runs alongside its `module.json`.
+    pub fn module_dir(&self, name: &str) -> Option<&Path> {
+        self.module_dirs.get(name).map(|p| p.as_path())
+    }
     
-    /// Retrieves a module by name
+    /// Retrieves a module by name, resolving `name` through the alias table
+    /// first if it matches one.
     pub fn get_module(&self, name: &str) -> Option<Arc<dyn SecVModule>> {
-        self.modules.get(name).cloned()
+        let resolved = self.aliases.resolve(name).map(|entry| entry.module.as_str()).unwrap_or(name);
+        self.modules.get(resolved).cloned()
+    }
+
+    /// Canned parameters an alias contributes, if `name` is an alias. The
+    /// caller is expected to merge these underneath its own `-p`/`--params`.
+    pub fn alias_params(&self, name: &str) -> Option<HashMap<String, serde_json::Value>> {
+        self.aliases.resolve(name).map(|entry| entry.params.clone())
+    }
+
+    /// Builds a `ModuleNotFound` error for `name`, appending a "did you
+    /// mean" suggestion when some loaded module is a close edit-distance
+    /// match — the same courtesy most CLI tools extend on a typo'd name.
+    pub fn not_found_error(&self, name: &str) -> SecVError {
+        match self.suggest(name) {
+            Some(suggestion) => SecVError::ModuleNotFound(
+                format!("{} (did you mean `{}`?)", name, suggestion)
+            ),
+            None => SecVError::ModuleNotFound(name.to_string()),
+        }
+    }
+
+    /// Finds the closest loaded module name to `name` by edit distance, if
+    /// one is within a small threshold (distance <= 3, or <= a third of
+    /// `name`'s length for longer names).
+    fn suggest(&self, name: &str) -> Option<String> {
+        let threshold = (name.len() / 3).max(3);
+        self.modules.keys()
+            .map(|candidate| (candidate, levenshtein(name, candidate)))
+            .filter(|(_, distance)| *distance <= threshold)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(candidate, _)| candidate.clone())
     }
     
     /// Returns all modules grouped by category
@@ -279,6 +559,144 @@ impl ModuleLoader {
     }
 }
 
+/// Classic edit-distance: module names are short, so the O(n*m) table is
+/// cheap enough to run on every miss without memoizing anything.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        let mut prev_diagonal = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let prev_row_j = row[j];
+            row[j] = if a[i - 1] == b[j - 1] {
+                prev_diagonal
+            } else {
+                1 + prev_diagonal.min(row[j]).min(row[j - 1])
+            };
+            prev_diagonal = prev_row_j;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Matches `text` against `pattern`, treating `*` as "zero or more
+/// characters" (case-insensitive). A pattern with no `*` falls back to a
+/// plain substring check, so `--filter scan` matches `port-scanner` without
+/// the user having to write `*scan*`.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let text = text.to_lowercase();
+
+    if !pattern.contains('*') {
+        return text.contains(&pattern);
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let mut cursor = 0;
+
+    if let Some(first) = parts.first() {
+        if !first.is_empty() {
+            if !text[cursor..].starts_with(first) {
+                return false;
+            }
+            cursor += first.len();
+        }
+    }
+
+    for part in &parts[1..parts.len().saturating_sub(1)] {
+        if part.is_empty() {
+            continue;
+        }
+        match text[cursor..].find(part) {
+            Some(offset) => cursor += offset + part.len(),
+            None => return false,
+        }
+    }
+
+    if let Some(last) = parts.last() {
+        if !last.is_empty() && parts.len() > 1 {
+            return text[cursor..].ends_with(last);
+        }
+    }
+
+    true
+}
+
+/// Whether a `SecVError` from `execute()` is worth retrying: timeouts,
+/// execution failures, and missing dependencies can all be transient, but a
+/// `ValidationFailed` means the caller's input was wrong and retrying it
+/// verbatim would just fail the same way again.
+fn is_retriable_error(err: &SecVError) -> bool {
+    matches!(err, SecVError::ExecutionFailed(_) | SecVError::DependencyMissing(_))
+}
+
+/// A short random suffix for default agent IDs (`secv agent` without `--id`).
+fn uuid_like_suffix() -> String {
+    let suffix: u32 = rand::thread_rng().gen();
+    format!("{:08x}", suffix)
+}
+
+/// Runs `attempt` up to `retries + 1` times, sleeping `delay` (doubling,
+/// capped at `max_delay`) between tries. A non-retriable `Err` is returned
+/// immediately with no retry. Once attempts are exhausted, a still-failing
+/// `Err` is turned into a synthetic failed `ModuleResult` so callers that
+/// branch on `ModuleResult.success` see it the same way as any other failed
+/// run rather than unwinding. Either way, the number of attempts taken is
+/// recorded in `ModuleResult.warnings`.
+async fn execute_with_retry<F, Fut>(
+    retries: u32,
+    mut delay: std::time::Duration,
+    max_delay: std::time::Duration,
+    mut attempt: F,
+) -> Result<ModuleResult, SecVError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<ModuleResult, SecVError>>,
+{
+    for try_number in 0..=retries {
+        let outcome = attempt().await;
+        let is_last_try = try_number == retries;
+
+        match outcome {
+            Ok(mut result) if result.success || is_last_try => {
+                if try_number > 0 {
+                    result.warnings.push(format!(
+                        "Succeeded after {} retr{}",
+                        try_number,
+                        if try_number == 1 { "y" } else { "ies" }
+                    ));
+                }
+                return Ok(result);
+            }
+            Err(e) if !is_retriable_error(&e) => return Err(e),
+            Err(e) if is_last_try => {
+                return Ok(ModuleResult {
+                    success: false,
+                    data: serde_json::Value::Null,
+                    errors: vec![e.to_string()],
+                    warnings: vec![format!("Failed after {} attempt(s)", try_number + 1)],
+                    execution_time_ms: 0,
+                    artifacts: Vec::new(),
+                });
+            }
+            Ok(_) | Err(_) => {
+                println!(
+                    "{}",
+                    format!("🔄 Attempt {} did not succeed; retrying in {:?}", try_number + 1, delay).yellow()
+                );
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(max_delay);
+            }
+        }
+    }
+
+    unreachable!("the try_number == retries arm above always returns on the final iteration")
+}
+
 /// Placeholder module implementation for demonstration
 pub struct PlaceholderModule {
     metadata: ModuleMetadata,
@@ -324,17 +742,22 @@ impl SecVModule for PlaceholderModule {
         Ok(())
     }
     
-    async fn execute(&self, context: ExecutionContext) -> Result<ModuleResult, SecVError> {
+    async fn execute(&self, context: ExecutionContext, cancel: CancellationToken) -> Result<ModuleResult, SecVError> {
         let start_time = std::time::Instant::now();
-        
+
         // Simulate module execution
-        println!("⚙️  Executing {} against {}", 
-                self.metadata.name.yellow().bold(), 
+        println!("⚙️  Executing {} against {}",
+                self.metadata.name.yellow().bold(),
                 context.target.green().bold());
-        
+
         // This is where real module logic would go
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-        
+        tokio::select! {
+            _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {}
+            _ = cancel.cancelled() => {
+                return Err(SecVError::ExecutionFailed("Execution cancelled".to_string()));
+            }
+        }
+
         let execution_time = start_time.elapsed();
         
         Ok(ModuleResult {
@@ -353,7 +776,7 @@ impl SecVModule for PlaceholderModule {
 }
 
 /// Workflow definition structure
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowDefinition {
     pub name: String,
     pub description: String,
@@ -363,7 +786,7 @@ pub struct WorkflowDefinition {
     pub global_settings: HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowStep {
     pub name: String,
     pub module: String,
@@ -371,23 +794,85 @@ pub struct WorkflowStep {
     pub condition: Option<String>,
     pub on_error: ErrorAction,
     pub timeout_seconds: Option<u64>,
+    /// Names of other steps that must complete before this one starts.
+    /// Steps with no shared dependency chain run concurrently.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Only meaningful when `on_error` is `Retry`: delay before the second
+    /// attempt, doubling (capped at `retry_max_delay_ms`) after each
+    /// subsequent failed attempt.
+    #[serde(default = "default_retry_initial_delay_ms")]
+    pub retry_initial_delay_ms: u64,
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub retry_max_delay_ms: u64,
+    /// Category a remote agent must advertise to run this step, used only
+    /// when `module` isn't loaded locally and the step is dispatched over
+    /// `AgentServer` instead. `None` matches any agent.
+    #[serde(default)]
+    pub required_category: Option<String>,
+    /// Capability tags a remote agent must advertise to run this step (same
+    /// dispatch path as `required_category`).
+    #[serde(default)]
+    pub required_capabilities: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+fn default_retry_initial_delay_ms() -> u64 {
+    500
+}
+
+fn default_retry_max_delay_ms() -> u64 {
+    30_000
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ErrorAction {
     Stop,
     Continue,
     Retry(u32),
 }
 
+/// Controls whether independent steps within a layer run in declared order
+/// or a randomized one. `Seeded`/`Random` both use the same deterministic
+/// `SmallRng`, so a run can be replayed exactly by passing the printed seed
+/// back via `--seed`.
+#[derive(Debug, Clone, Copy)]
+pub enum ShuffleMode {
+    Off,
+    Seeded(u64),
+    Random,
+}
+
 /// Advanced workflow engine with parallel execution capabilities
 pub struct WorkflowEngine {
     module_loader: Arc<ModuleLoader>,
+    /// When set, steps whose module isn't loaded locally are scheduled onto
+    /// a matching remote agent instead of failing with `ModuleNotFound`.
+    agent_server: Option<Arc<AgentServer>>,
+    /// When set, every locally-executed step's module capabilities/risk level
+    /// are checked against this policy before `SecVModule::execute` runs.
+    permission_policy: Option<Arc<PermissionPolicy>>,
 }
 
 impl WorkflowEngine {
     pub fn new(module_loader: Arc<ModuleLoader>) -> Self {
-        Self { module_loader }
+        Self {
+            module_loader,
+            agent_server: None,
+            permission_policy: None,
+        }
+    }
+
+    /// Enables distributed dispatch: steps targeting modules not loaded
+    /// locally are handed off to an idle remote agent via `agent_server`.
+    pub fn with_agent_server(mut self, agent_server: Arc<AgentServer>) -> Self {
+        self.agent_server = Some(agent_server);
+        self
+    }
+
+    /// Enables the capability/risk-level permission sandbox for local steps.
+    pub fn with_permission_policy(mut self, policy: Arc<PermissionPolicy>) -> Self {
+        self.permission_policy = Some(policy);
+        self
     }
     
     /// Loads a workflow from a file
@@ -409,73 +894,320 @@ impl WorkflowEngine {
         Ok(workflow)
     }
     
-    /// Executes a workflow with full context management
+    /// Executes a workflow with full context management.
+    ///
+    /// `cancel` is checked between steps (and passed into each module's
+    /// `execute`) so `--watch` mode can abort a stale run as soon as a newer
+    /// one is triggered. `filter` restricts execution to steps whose name or
+    /// module matches a glob/substring pattern, dropping dangling
+    /// `depends_on` edges onto steps that got filtered out. `shuffle`
+    /// randomizes the order of steps within each independent layer.
     pub async fn execute_workflow(
-        &self, 
-        workflow: WorkflowDefinition, 
-        target: String
+        &self,
+        workflow: WorkflowDefinition,
+        target: String,
+        cancel: CancellationToken,
+        filter: Option<&str>,
+        shuffle: ShuffleMode,
     ) -> Result<HashMap<String, ModuleResult>> {
         println!("{}", format!("🚀 Executing Workflow: {}", workflow.name).magenta().bold());
-        
-        let mut context = ExecutionContext {
-            target,
-            parameters: workflow.global_settings,
-            results: HashMap::new(),
-            metadata: HashMap::new(),
+
+        let steps = match filter {
+            Some(pattern) => {
+                let filtered = Self::filter_steps(&workflow.steps, pattern);
+                println!("{}", format!("🔍 Filter '{}' matched {} of {} step(s)",
+                        pattern, filtered.len(), workflow.steps.len()).cyan());
+                filtered
+            }
+            None => workflow.steps.clone(),
         };
-        
-        for (step_index, step) in workflow.steps.iter().enumerate() {
-            println!("\n{}", format!("--- Step {}: {} ---", step_index + 1, step.name).blue().bold());
-            
-            let module = self.module_loader.get_module(&step.module)
-                .ok_or_else(|| SecVError::ModuleNotFound(step.module.clone()))?;
-            
-            // Resolve dynamic inputs using context
-            let resolved_inputs = self.resolve_inputs(&step.inputs, &context)?;
-            context.parameters.extend(resolved_inputs);
-            
-            // Validate inputs before execution
-            module.validate_inputs(&context.parameters)?;
-            
-            // Execute with timeout if specified
-            let result = if let Some(timeout) = step.timeout_seconds {
-                tokio::time::timeout(
-                    tokio::time::Duration::from_secs(timeout),
-                    module.execute(context.clone())
-                ).await
-                .map_err(|_| SecVError::ExecutionFailed("Module execution timed out".to_string()))?
-            } else {
-                module.execute(context.clone()).await
-            }?;
-            
-            if result.success {
-                println!("{}", format!("✅ Step {} completed successfully", step_index + 1).green().bold());
-                context.results.insert(step.module.clone(), result);
-            } else {
-                match step.on_error {
-                    ErrorAction::Stop => {
-                        return Err(SecVError::WorkflowError(
-                            format!("Workflow stopped at step {} due to error", step_index + 1)
-                        ));
-                    },
-                    ErrorAction::Continue => {
-                        println!("{}", format!("⚠️  Step {} failed but continuing", step_index + 1).yellow());
-                        context.results.insert(step.module.clone(), result);
-                    },
-                    ErrorAction::Retry(max_retries) => {
-                        // Implement retry logic here
-                        println!("{}", format!("🔄 Retrying step {} (max {} attempts)", step_index + 1, max_retries).yellow());
-                        context.results.insert(step.module.clone(), result);
-                    },
+
+        let max_parallelism = workflow.global_settings.get("max_parallelism")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(4)
+            .max(1) as usize;
+
+        let mut layers = Self::build_layers(&steps)?;
+
+        let mut rng = match shuffle {
+            ShuffleMode::Off => None,
+            ShuffleMode::Seeded(seed) => {
+                println!("{}", format!("🔀 Shuffling independent steps with seed {}", seed).magenta());
+                Some(SmallRng::seed_from_u64(seed))
+            }
+            ShuffleMode::Random => {
+                let seed: u64 = rand::thread_rng().gen();
+                println!("{}", format!("🔀 Shuffling independent steps with seed {} (pass --seed {} to reproduce)",
+                        seed, seed).magenta());
+                Some(SmallRng::seed_from_u64(seed))
+            }
+        };
+        if let Some(rng) = &mut rng {
+            for layer in &mut layers {
+                layer.shuffle(rng);
+            }
+        }
+
+        let base_parameters = workflow.global_settings.clone();
+        let results: Arc<tokio::sync::Mutex<HashMap<String, ModuleResult>>> =
+            Arc::new(tokio::sync::Mutex::new(HashMap::new()));
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(max_parallelism));
+
+        for (layer_index, layer) in layers.iter().enumerate() {
+            if cancel.is_cancelled() {
+                return Err(SecVError::WorkflowError("Workflow cancelled".to_string()).into());
+            }
+
+            println!("\n{}", format!("--- Layer {} ({} step(s), up to {} concurrently) ---",
+                    layer_index + 1, layer.len(), max_parallelism).blue().bold());
+
+            // Results from prior layers are final by the time this layer starts,
+            // so every step in this layer sees a consistent snapshot to resolve inputs from.
+            let snapshot = results.lock().await.clone();
+
+            // Pre-resolve permission grants for this layer's steps serially,
+            // on this task, before any of them run concurrently below.
+            // PermissionPolicy::check prompts via a blocking stdin read, and
+            // the session-grant cache it fills in is keyed by capability (or
+            // module name for risk-level grants) rather than by step — so a
+            // capability granted here is already cached by the time each
+            // spawned step below calls check() again, and none of them ever
+            // reach .interact() concurrently with another on the same TTY.
+            if let Some(policy) = &self.permission_policy {
+                for &step_idx in layer {
+                    if let Some(module) = self.module_loader.get_module(&steps[step_idx].module) {
+                        policy.check(module.metadata()).await?;
+                    }
+                }
+            }
+
+            let mut join_set = tokio::task::JoinSet::new();
+            for &step_idx in layer {
+                let step = steps[step_idx].clone();
+                let context = ExecutionContext {
+                    target: target.clone(),
+                    parameters: base_parameters.clone(),
+                    results: snapshot.clone(),
+                    metadata: HashMap::new(),
+                };
+                let module_loader = self.module_loader.clone();
+                let agent_server = self.agent_server.clone();
+                let permission_policy = self.permission_policy.clone();
+                let permit = semaphore.clone();
+                let cancel = cancel.clone();
+                let engine_ref = WorkflowEngine {
+                    module_loader,
+                    agent_server,
+                    permission_policy,
+                };
+
+                join_set.spawn(async move {
+                    let _permit = permit.acquire_owned().await.expect("semaphore closed");
+                    println!("{}", format!("--- Step: {} ---", step.name).blue());
+
+                    let mut context = context;
+                    let resolved_inputs = engine_ref.resolve_inputs(&step.inputs, &context)
+                        .map_err(|e| SecVError::WorkflowError(e.to_string()))?;
+                    context.parameters.extend(resolved_inputs);
+
+                    let timeout_duration = tokio::time::Duration::from_secs(step.timeout_seconds.unwrap_or(300));
+
+                    let result = match engine_ref.module_loader.get_module(&step.module) {
+                        Some(module) => {
+                            module.validate_inputs(&context.parameters)?;
+
+                            if let Some(policy) = &engine_ref.permission_policy {
+                                policy.check(module.metadata()).await?;
+                            }
+
+                            let retries = match step.on_error {
+                                ErrorAction::Retry(max_retries) => max_retries,
+                                _ => 0,
+                            };
+                            let delay = std::time::Duration::from_millis(step.retry_initial_delay_ms);
+                            let max_delay = std::time::Duration::from_millis(step.retry_max_delay_ms);
+                            let timeout_seconds = step.timeout_seconds;
+                            let module = module.clone();
+                            let context = context.clone();
+                            let cancel = cancel.clone();
+
+                            execute_with_retry(retries, delay, max_delay, move || {
+                                let module = module.clone();
+                                let context = context.clone();
+                                let cancel = cancel.clone();
+                                async move {
+                                    if let Some(timeout) = timeout_seconds {
+                                        tokio::time::timeout(
+                                            tokio::time::Duration::from_secs(timeout),
+                                            module.execute(context, cancel)
+                                        ).await
+                                        .map_err(|_| SecVError::ExecutionFailed("Module execution timed out".to_string()))?
+                                    } else {
+                                        module.execute(context, cancel).await
+                                    }
+                                }
+                            }).await?
+                        }
+                        None => {
+                            let agent_server = engine_ref.agent_server.as_ref()
+                                .ok_or_else(|| engine_ref.module_loader.not_found_error(&step.module))?;
+                            agent_server
+                                .dispatch_step(&step, &context, timeout_duration)
+                                .await?
+                        }
+                    };
+
+                    Ok::<(WorkflowStep, ModuleResult), SecVError>((step, result))
+                });
+            }
+
+            while let Some(joined) = join_set.join_next().await {
+                let (step, result) = joined
+                    .map_err(|e| SecVError::ExecutionFailed(format!("Step task panicked: {}", e)))??;
+
+                if result.success {
+                    println!("{}", format!("✅ Step '{}' completed successfully", step.name).green().bold());
+                    results.lock().await.insert(step.name.clone(), result);
+                } else {
+                    match step.on_error {
+                        ErrorAction::Stop => {
+                            return Err(SecVError::WorkflowError(
+                                format!("Workflow stopped at step '{}' due to error", step.name)
+                            ).into());
+                        },
+                        ErrorAction::Continue => {
+                            println!("{}", format!("⚠️  Step '{}' failed but continuing", step.name).yellow());
+                            results.lock().await.insert(step.name.clone(), result);
+                        },
+                        ErrorAction::Retry(max_retries) => {
+                            println!("{}", format!("⚠️  Step '{}' still failing after {} attempt(s); continuing", step.name, max_retries + 1).yellow());
+                            results.lock().await.insert(step.name.clone(), result);
+                        },
+                    }
                 }
             }
         }
-        
+
         println!("\n{}", "🎉 Workflow completed successfully!".green().bold());
-        Ok(context.results)
+        let final_results = results.lock().await.clone();
+        Ok(final_results)
+    }
+
+    /// Keeps only the steps whose name or module matches `pattern`
+    /// (glob-or-substring), dropping `depends_on` edges that point at a
+    /// step which got filtered out rather than failing the whole run.
+    fn filter_steps(steps: &[WorkflowStep], pattern: &str) -> Vec<WorkflowStep> {
+        let mut matched: Vec<WorkflowStep> = steps.iter()
+            .filter(|step| glob_match(pattern, &step.name) || glob_match(pattern, &step.module))
+            .cloned()
+            .collect();
+
+        let matched_names: std::collections::HashSet<String> =
+            matched.iter().map(|step| step.name.clone()).collect();
+
+        for step in &mut matched {
+            step.depends_on.retain(|dep| matched_names.contains(dep));
+        }
+
+        matched
+    }
+
+    /// Builds a topologically-sorted layering of `steps` from their `depends_on`
+    /// edges (matched by step name). Each layer can run fully concurrently since
+    /// none of its steps depend on one another. Fails fast on an unknown
+    /// dependency name or a cycle.
+    fn build_layers(steps: &[WorkflowStep]) -> Result<Vec<Vec<usize>>> {
+        let name_to_index: HashMap<&str, usize> = steps.iter()
+            .enumerate()
+            .map(|(i, s)| (s.name.as_str(), i))
+            .collect();
+
+        for step in steps {
+            for dep in &step.depends_on {
+                if !name_to_index.contains_key(dep.as_str()) {
+                    return Err(SecVError::WorkflowError(
+                        format!("Step '{}' depends on unknown step '{}'", step.name, dep)
+                    ).into());
+                }
+            }
+        }
+
+        // Each step's effective dependency set is its explicit `depends_on`
+        // plus any step referenced via `${results.<step>.<field>}` in its
+        // inputs — resolving that reference requires the referenced step to
+        // have already run, so the scheduler must wait on it even when the
+        // workflow author forgot to also list it in `depends_on`.
+        let dependencies: Vec<Vec<usize>> = steps.iter().map(|step| {
+            let mut deps: std::collections::BTreeSet<usize> = step.depends_on.iter()
+                .filter_map(|dep| name_to_index.get(dep.as_str()).copied())
+                .collect();
+            for referenced in Self::referenced_step_names(&step.inputs) {
+                if let Some(&idx) = name_to_index.get(referenced.as_str()) {
+                    deps.insert(idx);
+                }
+            }
+            deps.into_iter().collect()
+        }).collect();
+
+        let mut in_degree: Vec<usize> = dependencies.iter().map(|d| d.len()).collect();
+        let mut remaining: std::collections::HashSet<usize> = (0..steps.len()).collect();
+        let mut layers = Vec::new();
+
+        while !remaining.is_empty() {
+            // Sorted so that, for a given workflow, the pre-shuffle order is
+            // the same on every run — `remaining` is a HashSet, so without
+            // this the iteration order (and therefore the seeded shuffle's
+            // result) would vary run to run even for the same --seed.
+            let mut layer: Vec<usize> = remaining.iter()
+                .copied()
+                .filter(|&i| in_degree[i] == 0)
+                .collect();
+            layer.sort_unstable();
+
+            if layer.is_empty() {
+                let stuck: Vec<&str> = remaining.iter().map(|&i| steps[i].name.as_str()).collect();
+                return Err(SecVError::WorkflowError(
+                    format!("Cycle detected in workflow dependencies among steps: {}", stuck.join(", "))
+                ).into());
+            }
+
+            for &i in &layer {
+                remaining.remove(&i);
+            }
+            for &i in &remaining {
+                for &dep in &dependencies[i] {
+                    if layer.contains(&dep) {
+                        in_degree[i] -= 1;
+                    }
+                }
+            }
+
+            layers.push(layer);
+        }
+
+        Ok(layers)
+    }
+
+    /// Scans a step's inputs for `${results.<step>.<field>}` references,
+    /// the same pattern `resolve_inputs` resolves, and returns the
+    /// referenced step names — used by `build_layers` to derive implicit
+    /// DAG edges.
+    fn referenced_step_names(inputs: &HashMap<String, serde_json::Value>) -> Vec<String> {
+        inputs.values()
+            .filter_map(|value| match value {
+                serde_json::Value::String(s) if s.starts_with("${") && s.ends_with('}') => {
+                    let path = &s[2..s.len() - 1];
+                    match path.split('.').collect::<Vec<&str>>().as_slice() {
+                        ["results", step_name, _field] => Some(step_name.to_string()),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect()
     }
     
-    /// Resolves dynamic input references like ${results.scanner.ports}
+    /// Resolves dynamic input references like ${results.scan-step.ports}
     fn resolve_inputs(
         &self, 
         inputs: &HashMap<String, serde_json::Value>, 
@@ -501,19 +1233,21 @@ impl WorkflowEngine {
         Ok(resolved)
     }
     
-    /// Resolves a dot-notation path in the execution context
+    /// Resolves a dot-notation path in the execution context. `results` is
+    /// keyed by step name (not module name), matching how `execute_workflow`
+    /// populates `context.results`.
     fn resolve_context_path(&self, path: &str, context: &ExecutionContext) -> Result<serde_json::Value> {
         let parts: Vec<&str> = path.split('.').collect();
-        
+
         match parts.as_slice() {
             ["target"] => Ok(serde_json::Value::String(context.target.clone())),
-            ["results", module_name, field] => {
-                if let Some(result) = context.results.get(*module_name) {
+            ["results", step_name, field] => {
+                if let Some(result) = context.results.get(*step_name) {
                     result.data.get(field)
                         .cloned()
-                        .ok_or_else(|| anyhow::anyhow!("Field '{}' not found in module '{}' results", field, module_name))
+                        .ok_or_else(|| anyhow::anyhow!("Field '{}' not found in step '{}' results", field, step_name))
                 } else {
-                    Err(anyhow::anyhow!("Module '{}' results not found", module_name))
+                    Err(anyhow::anyhow!("Step '{}' results not found", step_name))
                 }
             },
             _ => Err(anyhow::anyhow!("Invalid context path: {}", path)),
@@ -525,15 +1259,20 @@ impl WorkflowEngine {
 pub struct InteractiveInterface {
     module_loader: Arc<ModuleLoader>,
     workflow_engine: Arc<WorkflowEngine>,
+    permission_policy: Arc<PermissionPolicy>,
 }
 
 impl InteractiveInterface {
-    pub fn new(module_loader: Arc<ModuleLoader>) -> Self {
-        let workflow_engine = Arc::new(WorkflowEngine::new(module_loader.clone()));
-        
+    pub fn new(module_loader: Arc<ModuleLoader>, permission_policy: Arc<PermissionPolicy>) -> Self {
+        let workflow_engine = Arc::new(
+            WorkflowEngine::new(module_loader.clone())
+                .with_permission_policy(permission_policy.clone()),
+        );
+
         Self {
             module_loader,
             workflow_engine,
+            permission_policy,
         }
     }
     
@@ -634,7 +1373,7 @@ impl InteractiveInterface {
         
         let module_name = &module_names[selection];
         let module = self.module_loader.get_module(module_name)
-            .ok_or_else(|| SecVError::ModuleNotFound(module_name.clone()))?;
+            .ok_or_else(|| self.module_loader.not_found_error(module_name))?;
         
         // Collect inputs
         let target: String = Input::new()
@@ -658,10 +1397,12 @@ impl InteractiveInterface {
             metadata: HashMap::new(),
         };
         
+        self.permission_policy.check(module.metadata()).await?;
+
         println!("\n{}", format!("⚙️  Executing {}...", module_name).cyan().bold());
-        
-        let result = module.execute(context).await?;
-        
+
+        let result = module.execute(context, CancellationToken::new()).await?;
+
         if result.success {
             println!("{}", format!("✅ Execution completed in {}ms", result.execution_time_ms).green().bold());
             println!("Result: {}", serde_json::to_string_pretty(&result.data)?);
@@ -687,7 +1428,9 @@ impl InteractiveInterface {
             .interact_text()?;
         
         let workflow = self.workflow_engine.load_workflow(&PathBuf::from(workflow_path)).await?;
-        let results = self.workflow_engine.execute_workflow(workflow, target).await?;
+        let results = self.workflow_engine.execute_workflow(
+            workflow, target, CancellationToken::new(), None, ShuffleMode::Off
+        ).await?;
         
         println!("\n{}", "--- Final Results ---".blue().bold());
         for (module_name, result) in results {
@@ -714,7 +1457,7 @@ impl InteractiveInterface {
         
         let module_name = &module_names[selection];
         let module = self.module_loader.get_module(module_name)
-            .ok_or_else(|| SecVError::ModuleNotFound(module_name.clone()))?;
+            .ok_or_else(|| self.module_loader.not_found_error(module_name))?;
         
         let metadata = module.metadata();
         
@@ -819,6 +1562,7 @@ async fn initialize_structure() -> Result<()> {
         },
         capabilities: vec!["network-scanning".to_string(), "port-detection".to_string()],
         risk_level: RiskLevel::Low,
+        entry_point: None,
     };
     
     let metadata_json = serde_json::to_string_pretty(&example_metadata)?;
@@ -826,10 +1570,49 @@ async fn initialize_structure() -> Result<()> {
     
     println!("{}", "✅ Directory structure initialized successfully!".green().bold());
     println!("{}", "You can now add modules to the tools/ directory.".white());
-    
+
+    // Best-effort: a non-interactive `--init` (e.g. in a CI provisioning
+    // step) has no terminal to prompt on, so a prompt failure here just
+    // skips the wizard instead of failing the whole init.
+    let wants_wizard = Confirm::new()
+        .with_prompt("Launch the module wizard now to scaffold your first real module?")
+        .default(false)
+        .interact()
+        .unwrap_or(false);
+    if wants_wizard {
+        wizard::new_module().await?;
+    }
+
     Ok(())
 }
 
+/// Builds the permission policy for this run from CLI flags, merged with an
+/// optional policy file.
+async fn build_permission_policy(cli: &SecVCli) -> Result<Arc<PermissionPolicy>> {
+    let mut allow = Vec::new();
+    let mut deny = Vec::new();
+
+    if cli.allow_net { allow.push("net".to_string()); }
+    if cli.allow_process { allow.push("process-spawn".to_string()); }
+    if cli.allow_fs_read { allow.push("fs-read".to_string()); }
+    if cli.allow_fs_write { allow.push("fs-write".to_string()); }
+    if cli.allow_raw_socket { allow.push("raw-socket".to_string()); }
+
+    if cli.deny_net { deny.push("net".to_string()); }
+    if cli.deny_process { deny.push("process-spawn".to_string()); }
+    if cli.deny_fs_read { deny.push("fs-read".to_string()); }
+    if cli.deny_fs_write { deny.push("fs-write".to_string()); }
+    if cli.deny_raw_socket { deny.push("raw-socket".to_string()); }
+
+    if let Some(policy_file) = &cli.policy_file {
+        let mut policy = PermissionPolicy::from_file(policy_file, !cli.no_prompt).await?;
+        policy.extend(allow, deny);
+        return Ok(Arc::new(policy));
+    }
+
+    Ok(Arc::new(PermissionPolicy::new(allow, deny, !cli.no_prompt)))
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = SecVCli::parse();
@@ -841,63 +1624,187 @@ async fn main() -> Result<()> {
     
     let mut module_loader = ModuleLoader::new("tools");
     module_loader.discover_modules().await?;
+    module_loader.load_aliases().await?;
     let module_loader = Arc::new(module_loader);
-    
+
+    let permission_policy = build_permission_policy(&cli).await?;
+
     match cli.command {
-        Some(Commands::Execute { module, target, params }) => {
-            let module_instance = module_loader.get_module(&module)
-                .ok_or_else(|| SecVError::ModuleNotFound(module.clone()))?;
-            
-            let mut parameters = HashMap::new();
-            if let Some(params_str) = params {
-                parameters = serde_json::from_str(&params_str)
+        Some(Commands::Execute { module, target, params, watch, report, report_out, retries, retry_backoff }) => {
+            // An alias's canned parameters are defaults; anything the user
+            // passes via `-p`/`--params` takes precedence over them.
+            let mut parameters = module_loader.alias_params(&module).unwrap_or_default();
+            if let Some(params_str) = &params {
+                let explicit: HashMap<String, serde_json::Value> = serde_json::from_str(params_str)
                     .context("Failed to parse parameters JSON")?;
+                parameters.extend(explicit);
             }
-            
-            let context = ExecutionContext {
-                target,
-                parameters,
-                results: HashMap::new(),
-                metadata: HashMap::new(),
-            };
-            
-            println!("{}", format!("⚙️  Executing {} against {}", 
-                    module.yellow().bold(), context.target.green().bold()));
-            
-            let result = module_instance.execute(context).await?;
-            
-            if result.success {
-                println!("{}", format!("✅ Execution completed in {}ms", 
-                        result.execution_time_ms).green().bold());
-                println!("{}", serde_json::to_string_pretty(&result.data)?);
-            } else {
-                println!("{}", "❌ Execution failed".red().bold());
-                for error in &result.errors {
-                    println!("  {}: {}", "Error".red().bold(), error);
+
+            loop {
+                let module_instance = module_loader.get_module(&module)
+                    .ok_or_else(|| module_loader.not_found_error(&module))?;
+
+                permission_policy.check(module_instance.metadata()).await?;
+
+                let context = ExecutionContext {
+                    target: target.clone(),
+                    parameters: parameters.clone(),
+                    results: HashMap::new(),
+                    metadata: HashMap::new(),
+                };
+
+                println!("{}", format!("⚙️  Executing {} against {}",
+                        module.yellow().bold(), context.target.green().bold()));
+
+                let module_for_retry = module_instance.clone();
+                let result = execute_with_retry(
+                    retries,
+                    std::time::Duration::from_millis(retry_backoff),
+                    std::time::Duration::from_secs(30),
+                    move || {
+                        let module = module_for_retry.clone();
+                        let context = context.clone();
+                        async move { module.execute(context, CancellationToken::new()).await }
+                    },
+                ).await?;
+
+                if result.success {
+                    println!("{}", format!("✅ Execution completed in {}ms",
+                            result.execution_time_ms).green().bold());
+                    println!("{}", serde_json::to_string_pretty(&result.data)?);
+                } else {
+                    println!("{}", "❌ Execution failed".red().bold());
+                    for error in &result.errors {
+                        println!("  {}: {}", "Error".red().bold(), error);
+                    }
                 }
-                std::process::exit(1);
+
+                if let (Some(format), Some(path)) = (&report, &report_out) {
+                    let results = HashMap::from([(module.clone(), result.clone())]);
+                    report::write_report(format, &module, &results, path).await?;
+                    println!("{} {}", "📝 Report written to".cyan(), path.display());
+                }
+
+                if !result.success && !watch {
+                    std::process::exit(1);
+                }
+
+                if !watch {
+                    break;
+                }
+
+                println!("\n{}", "👀 Watching for changes... (Ctrl+C to stop)".magenta().bold());
+                // `module` may be an alias, and modules are discovered one
+                // level under `tools/` with an arbitrary directory name, so
+                // the real path has to come from the loader's own record of
+                // where it found the module rather than being guessed.
+                let watch_paths: Vec<PathBuf> = module_loader
+                    .module_dir(&module_instance.metadata().name)
+                    .map(|dir| dir.join("module.json"))
+                    .into_iter()
+                    .collect();
+                wait_for_change(&watch_paths).await?;
             }
         },
-        
-        Some(Commands::Workflow { file, target }) => {
-            let workflow_engine = WorkflowEngine::new(module_loader.clone());
-            let workflow = workflow_engine.load_workflow(&file).await?;
-            let results = workflow_engine.execute_workflow(workflow, target).await?;
-            
-            println!("\n{}", "--- Workflow Results Summary ---".blue().bold());
-            for (module_name, result) in results {
-                let status = if result.success {
-                    format!("✅ Success ({}ms)", result.execution_time_ms).green()
+
+        Some(Commands::Workflow { file, target, watch, report, report_out, filter, shuffle, seed, agent_bind, heartbeat_interval }) => {
+            let mut workflow_engine = WorkflowEngine::new(module_loader.clone())
+                .with_permission_policy(permission_policy.clone());
+
+            if let Some(agent_bind) = &agent_bind {
+                let agent_server = Arc::new(AgentServer::new());
+                agent_server.clone().spawn_heartbeat_sweep(std::time::Duration::from_secs(heartbeat_interval));
+                let bind_server = agent_server.clone();
+                let bind_addr = agent_bind.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = bind_server.bind(&bind_addr).await {
+                        eprintln!("{}", format!("Agent server exited: {}", e).red());
+                    }
+                });
+                workflow_engine = workflow_engine.with_agent_server(agent_server);
+            }
+
+            let mut previous_results: HashMap<String, ModuleResult> = HashMap::new();
+
+            let shuffle_mode = match seed {
+                Some(seed) => ShuffleMode::Seeded(seed),
+                None if shuffle => ShuffleMode::Random,
+                None => ShuffleMode::Off,
+            };
+
+            loop {
+                let workflow = workflow_engine.load_workflow(&file).await?;
+                let workflow_name = workflow.name.clone();
+
+                // Watch the workflow file itself plus every loaded step's
+                // module.json, so editing either triggers a re-run (a step
+                // whose module isn't loaded locally has nothing to watch).
+                // `step.module` may be an alias, so resolve it through
+                // get_module to the module's canonical name first --
+                // module_dir is keyed by that name, not by alias.
+                let mut watch_paths = vec![file.clone()];
+                for step in &workflow.steps {
+                    if let Some(module) = module_loader.get_module(&step.module) {
+                        if let Some(dir) = module_loader.module_dir(&module.metadata().name) {
+                            watch_paths.push(dir.join("module.json"));
+                        }
+                    }
+                }
+
+                let cancel = CancellationToken::new();
+                let run = workflow_engine.execute_workflow(
+                    workflow, target.clone(), cancel.clone(), filter.as_deref(), shuffle_mode
+                );
+                tokio::pin!(run);
+
+                let results = if watch {
+                    tokio::select! {
+                        result = &mut run => result?,
+                        _ = wait_for_change(&watch_paths) => {
+                            // A change landed mid-run: cancel it and let the
+                            // next loop iteration pick up the new
+                            // workflow/module definitions instead of
+                            // printing stale results.
+                            cancel.cancel();
+                            let _ = run.await;
+                            println!("\n{}", "🔄 Change detected mid-run, restarting...".magenta().bold());
+                            continue;
+                        }
+                    }
                 } else {
-                    "❌ Failed".red()
+                    run.await?
                 };
-                println!("{}: {}", module_name.cyan().bold(), status);
-                
-                if !result.warnings.is_empty() {
-                    for warning in &result.warnings {
-                        println!("  ⚠️  {}", warning.yellow());
+
+                println!("\n{}", "--- Workflow Results Summary ---".blue().bold());
+                for (module_name, result) in &results {
+                    let status = if result.success {
+                        format!("✅ Success ({}ms)", result.execution_time_ms).green()
+                    } else {
+                        "❌ Failed".red()
+                    };
+                    println!("{}: {}", module_name.cyan().bold(), status);
+
+                    if !result.warnings.is_empty() {
+                        for warning in &result.warnings {
+                            println!("  ⚠️  {}", warning.yellow());
+                        }
                     }
                 }
+
+                if let (Some(format), Some(path)) = (&report, &report_out) {
+                    report::write_report(format, &workflow_name, &results, path).await?;
+                    println!("{} {}", "📝 Report written to".cyan(), path.display());
+                }
+
+                if !watch {
+                    break;
+                }
+
+                print_diff_summary(&previous_results, &results);
+                previous_results = results;
+
+                println!("\n{}", "👀 Watching for changes... (Ctrl+C to stop)".magenta().bold());
+                wait_for_change(&watch_paths).await?;
             }
         },
         
@@ -942,7 +1849,7 @@ async fn main() -> Result<()> {
         
         Some(Commands::Info { module }) => {
             let module_instance = module_loader.get_module(&module)
-                .ok_or_else(|| SecVError::ModuleNotFound(module.clone()))?;
+                .ok_or_else(|| module_loader.not_found_error(&module))?;
             
             let metadata = module_instance.metadata();
             
@@ -964,7 +1871,11 @@ async fn main() -> Result<()> {
             if !metadata.capabilities.is_empty() {
                 println!("  Capabilities: {}", metadata.capabilities.join(", ").green());
             }
-            
+
+            if let Some(entry_point) = &metadata.entry_point {
+                println!("  Native entry point: {}", entry_point.magenta());
+            }
+
             println!("\n{}:", "Inputs".yellow().bold());
             for (key, spec) in &metadata.inputs {
                 let required_badge = if spec.required { 
@@ -996,9 +1907,158 @@ async fn main() -> Result<()> {
                 Err(e) => println!("{}: {}", "❌ Error".red().bold(), e),
             }
         },
-        
+
+        Some(Commands::Test { module }) => {
+            let targets = match &module {
+                Some(name) => vec![name.clone()],
+                None => module_loader.module_names(),
+            };
+
+            if targets.is_empty() {
+                println!("{}", "No modules loaded to test.".yellow());
+                return Ok(());
+            }
+
+            println!("\n{}", "--- Running module fixtures ---".blue().bold());
+            let mut all_passed = true;
+            for name in &targets {
+                let module_instance = module_loader.get_module(name)
+                    .ok_or_else(|| module_loader.not_found_error(name))?;
+                let module_dir = module_loader.module_dir(name)
+                    .ok_or_else(|| anyhow::anyhow!("No directory on record for module '{}'", name))?;
+
+                let results = test::run_cases(&module_instance, module_dir).await?;
+                if results.is_empty() {
+                    continue;
+                }
+                if !test::print_summary(name, &results) {
+                    all_passed = false;
+                }
+            }
+
+            if !all_passed {
+                std::process::exit(1);
+            }
+        },
+
+        Some(Commands::Bench { module, workflow, target, params, iterations, warmup, out, compare, threshold }) => {
+            let (subject, dependencies, samples) = match (module, workflow) {
+                (Some(module_name), None) => {
+                    let module_instance = module_loader.get_module(&module_name)
+                        .ok_or_else(|| module_loader.not_found_error(&module_name))?;
+                    let dependencies = module_instance.metadata().dependencies.clone();
+
+                    let mut parameters = module_loader.alias_params(&module_name).unwrap_or_default();
+                    if let Some(params_str) = &params {
+                        let explicit: HashMap<String, serde_json::Value> = serde_json::from_str(params_str)
+                            .context("Failed to parse parameters JSON")?;
+                        parameters.extend(explicit);
+                    }
+
+                    println!("{}", format!("⏱️  Benchmarking module '{}' against {}", module_name, target).cyan().bold());
+                    let samples = bench::run_module_bench(
+                        module_instance, &permission_policy, target.clone(), parameters, iterations, warmup
+                    ).await?;
+                    (module_name, dependencies, samples)
+                }
+                (None, Some(workflow_path)) => {
+                    let workflow_engine = WorkflowEngine::new(module_loader.clone())
+                        .with_permission_policy(permission_policy.clone());
+                    let workflow_def = workflow_engine.load_workflow(&workflow_path).await?;
+
+                    println!("{}", format!("⏱️  Benchmarking workflow '{}' against {}", workflow_def.name, target).cyan().bold());
+                    let samples = bench::run_workflow_bench(
+                        &workflow_engine, &workflow_def, target.clone(), iterations, warmup
+                    ).await?;
+                    (workflow_def.name.clone(), Vec::new(), samples)
+                }
+                (Some(_), Some(_)) => return Err(anyhow::anyhow!("Pass either --module or --workflow, not both")),
+                (None, None) => return Err(anyhow::anyhow!("Pass --module or --workflow to benchmark")),
+            };
+
+            let stats = bench::LatencyStats::from_samples(samples)?;
+            let environment = bench::capture_environment(&dependencies).await;
+
+            println!("\n{}", "--- Latency (ms) ---".blue().bold());
+            println!("  min: {:.2}  max: {:.2}  mean: {:.2}  median: {:.2}  p95: {:.2}  stddev: {:.2}",
+                    stats.min_ms, stats.max_ms, stats.mean_ms, stats.median_ms, stats.p95_ms, stats.stddev_ms);
+
+            if let Some(compare_path) = &compare {
+                let previous: bench::BenchReport = serde_json::from_str(
+                    &fs::read_to_string(compare_path).await.context("Failed to read --compare report")?
+                ).context("Failed to parse --compare report")?;
+
+                match bench::diff_against(&previous, &stats, threshold) {
+                    Some(regression) => println!("{}", format!("📉 {}", regression).red().bold()),
+                    None => println!("{}", "📈 No regression above threshold".green()),
+                }
+            }
+
+            let report = bench::BenchReport { subject, target, iterations, warmup, stats, environment };
+
+            if let Some(out_path) = &out {
+                fs::write(out_path, serde_json::to_string_pretty(&report)?).await?;
+                println!("{} {}", "📝 Report written to".cyan(), out_path.display());
+            }
+        },
+
+        Some(Commands::Serve { bind, token }) => {
+            // No TTY to prompt on here: force non-interactive so an
+            // unresolved capability is a clean 403 instead of every request
+            // failing with "Prompt failed".
+            let server_policy = Arc::new(permission_policy.non_interactive());
+            server::serve(&bind, token, module_loader.clone(), server_policy, None).await?;
+        },
+
+        Some(Commands::ServeAgents { agent_bind, control_bind, token, heartbeat_interval }) => {
+            let agent_server = Arc::new(AgentServer::new());
+            agent_server.clone().spawn_heartbeat_sweep(std::time::Duration::from_secs(heartbeat_interval));
+
+            let bind_server = agent_server.clone();
+            let bind_addr = agent_bind.clone();
+            tokio::spawn(async move {
+                if let Err(e) = bind_server.bind(&bind_addr).await {
+                    eprintln!("{}", format!("Agent server exited: {}", e).red());
+                }
+            });
+
+            let server_policy = Arc::new(permission_policy.non_interactive());
+            server::serve(&control_bind, token, module_loader.clone(), server_policy, Some(agent_server)).await?;
+        },
+
+        Some(Commands::Daemon { bind, token, health_interval, workflows_dir }) => {
+            daemon::run(
+                module_loader.clone(),
+                Arc::new(permission_policy.non_interactive()),
+                bind,
+                token,
+                std::time::Duration::from_secs(health_interval),
+                workflows_dir,
+            ).await?;
+        },
+
+        Some(Commands::Agent { server, id, category, capabilities, heartbeat_interval }) => {
+            let agent_id = id.unwrap_or_else(|| format!("agent-{}", uuid_like_suffix()));
+            agent::run_client(
+                &server,
+                agent_id,
+                category,
+                capabilities,
+                module_loader.clone(),
+                std::time::Duration::from_secs(heartbeat_interval),
+            ).await?;
+        },
+
+        Some(Commands::New { target: NewTarget::Module }) => {
+            wizard::new_module().await?;
+        },
+
+        Some(Commands::New { target: NewTarget::Workflow }) => {
+            wizard::new_workflow(&module_loader).await?;
+        },
+
         Some(Commands::Interactive) | None => {
-            let interface = InteractiveInterface::new(module_loader);
+            let interface = InteractiveInterface::new(module_loader, permission_policy.clone());
             interface.run().await?;
         },
     }
@@ -1076,12 +2136,19 @@ impl NetworkScannerModule {
                 "os-fingerprinting".to_string(),
             ],
             risk_level: RiskLevel::Low,
+            entry_point: None,
         };
 
         Self { metadata }
     }
 }
 
+impl Default for NetworkScannerModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl SecVModule for NetworkScannerModule {
     fn metadata(&self) -> &ModuleMetadata {
@@ -1110,22 +2177,20 @@ impl SecVModule for NetworkScannerModule {
         }
 
         // Validate port range format if provided
-        if let Some(ports) = inputs.get("ports") {
-            if let serde_json::Value::String(port_str) = ports {
-                if !port_str.matches(char::is_numeric).any() {
-                    return Err(SecVError::ValidationFailed(
-                        "Invalid port range format".to_string()
-                    ));
-                }
+        if let Some(serde_json::Value::String(port_str)) = inputs.get("ports") {
+            if !port_str.chars().any(char::is_numeric) {
+                return Err(SecVError::ValidationFailed(
+                    "Invalid port range format".to_string()
+                ));
             }
         }
 
         Ok(())
     }
 
-    async fn execute(&self, context: ExecutionContext) -> Result<ModuleResult, SecVError> {
+    async fn execute(&self, context: ExecutionContext, cancel: CancellationToken) -> Result<ModuleResult, SecVError> {
         let start_time = std::time::Instant::now();
-        
+
         let target = context.parameters.get("target")
             .and_then(|v| v.as_str())
             .ok_or_else(|| SecVError::ValidationFailed("Target not provided".to_string()))?;
@@ -1157,8 +2222,14 @@ impl SecVModule for NetworkScannerModule {
            .arg(target);
 
         // Execute scan
-        let output = cmd.output().await
-            .map_err(|e| SecVError::ExecutionFailed(format!("Failed to execute nmap: {}", e)))?;
+        let output = tokio::select! {
+            result = cmd.output() => {
+                result.map_err(|e| SecVError::ExecutionFailed(format!("Failed to execute nmap: {}", e)))?
+            }
+            _ = cancel.cancelled() => {
+                return Err(SecVError::ExecutionFailed("Scan cancelled".to_string()));
+            }
+        };
 
         let execution_time = start_time.elapsed();
         
@@ -1222,3 +2293,162 @@ impl SecVModule for NetworkScannerModule {
         Ok(())
     }
 }
+
+
+#[cfg(test)]
+mod workflow_engine_tests {
+    use super::*;
+
+    fn test_module(name: &str) -> Arc<dyn SecVModule> {
+        Arc::new(PlaceholderModule::new(ModuleMetadata {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            category: "test".to_string(),
+            description: "test module".to_string(),
+            author: "test".to_string(),
+            dependencies: vec![],
+            inputs: HashMap::new(),
+            outputs: HashMap::new(),
+            capabilities: vec![],
+            risk_level: RiskLevel::Low,
+            entry_point: None,
+        }))
+    }
+
+    fn test_engine(modules: Vec<Arc<dyn SecVModule>>) -> WorkflowEngine {
+        let mut module_loader = ModuleLoader::new("tools");
+        for module in modules {
+            module_loader.modules.insert(module.metadata().name.clone(), module);
+        }
+        WorkflowEngine::new(Arc::new(module_loader))
+    }
+
+    /// Regression test for the step-name/module-name keying regression:
+    /// `results` must be addressable as `${results.<step name>.<field>}`
+    /// even when a step's name differs from the module it runs.
+    #[tokio::test]
+    async fn passes_results_between_steps_with_distinct_names() {
+        let engine = test_engine(vec![test_module("scanner-module"), test_module("reporter-module")]);
+
+        let workflow = WorkflowDefinition {
+            name: "chain".to_string(),
+            description: "".to_string(),
+            version: "1.0.0".to_string(),
+            author: "test".to_string(),
+            global_settings: HashMap::new(),
+            steps: vec![
+                WorkflowStep {
+                    name: "scan-step".to_string(),
+                    module: "scanner-module".to_string(),
+                    inputs: HashMap::new(),
+                    condition: None,
+                    on_error: ErrorAction::Stop,
+                    timeout_seconds: None,
+                    depends_on: vec![],
+                    retry_initial_delay_ms: 500,
+                    retry_max_delay_ms: 30_000,
+                    required_category: None,
+                    required_capabilities: vec![],
+                },
+                WorkflowStep {
+                    name: "report-step".to_string(),
+                    module: "reporter-module".to_string(),
+                    inputs: HashMap::from([(
+                        "upstream_message".to_string(),
+                        serde_json::Value::String("${results.scan-step.message}".to_string()),
+                    )]),
+                    condition: None,
+                    on_error: ErrorAction::Stop,
+                    timeout_seconds: None,
+                    depends_on: vec!["scan-step".to_string()],
+                    retry_initial_delay_ms: 500,
+                    retry_max_delay_ms: 30_000,
+                    required_category: None,
+                    required_capabilities: vec![],
+                },
+            ],
+        };
+
+        let results = engine
+            .execute_workflow(workflow, "127.0.0.1".to_string(), CancellationToken::new(), None, ShuffleMode::Off)
+            .await
+            .expect("workflow should complete");
+
+        assert!(results.contains_key("scan-step"));
+        assert!(results.contains_key("report-step"));
+    }
+
+    #[test]
+    fn resolve_context_path_looks_up_by_step_name() {
+        let engine = test_engine(vec![]);
+
+        let mut results = HashMap::new();
+        results.insert(
+            "scan-step".to_string(),
+            ModuleResult {
+                success: true,
+                data: serde_json::json!({ "ports": [80, 443] }),
+                errors: vec![],
+                warnings: vec![],
+                execution_time_ms: 0,
+                artifacts: vec![],
+            },
+        );
+        let context = ExecutionContext {
+            target: "127.0.0.1".to_string(),
+            parameters: HashMap::new(),
+            results,
+            metadata: HashMap::new(),
+        };
+
+        let resolved = engine
+            .resolve_context_path("results.scan-step.ports", &context)
+            .expect("should resolve by step name");
+        assert_eq!(resolved, serde_json::json!([80, 443]));
+
+        let err = engine.resolve_context_path("results.scanner-module.ports", &context);
+        assert!(err.is_err(), "module name (as opposed to step name) must not resolve");
+    }
+
+    /// A step that reads `${results.<upstream>.<field>}` must wait for
+    /// `<upstream>` even if the workflow author forgot to also declare it
+    /// in `depends_on` — otherwise the two steps could land in the same
+    /// layer (or the wrong order) and the reference would fail to resolve.
+    #[test]
+    fn build_layers_infers_edge_from_results_reference_without_depends_on() {
+        let steps = vec![
+            WorkflowStep {
+                name: "scan-step".to_string(),
+                module: "scanner-module".to_string(),
+                inputs: HashMap::new(),
+                condition: None,
+                on_error: ErrorAction::Stop,
+                timeout_seconds: None,
+                depends_on: vec![],
+                retry_initial_delay_ms: 500,
+                retry_max_delay_ms: 30_000,
+                required_category: None,
+                required_capabilities: vec![],
+            },
+            WorkflowStep {
+                name: "report-step".to_string(),
+                module: "reporter-module".to_string(),
+                inputs: HashMap::from([(
+                    "upstream_message".to_string(),
+                    serde_json::Value::String("${results.scan-step.message}".to_string()),
+                )]),
+                condition: None,
+                on_error: ErrorAction::Stop,
+                timeout_seconds: None,
+                depends_on: vec![], // deliberately missing, unlike the test above
+                retry_initial_delay_ms: 500,
+                retry_max_delay_ms: 30_000,
+                required_category: None,
+                required_capabilities: vec![],
+            },
+        ];
+
+        let layers = WorkflowEngine::build_layers(&steps).expect("should build layers");
+        assert_eq!(layers, vec![vec![0], vec![1]], "report-step must land in a later layer than scan-step");
+    }
+}
\ No newline at end of file
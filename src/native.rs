@@ -0,0 +1,202 @@
+//! Stable C ABI for dynamically-loaded native modules.
+//!
+//! A module directory can ship a compiled shared library (`entry_point` in
+//! `module.json`) instead of being purely declarative. The ABI is
+//! deliberately narrow — a version check plus JSON-in/JSON-out `execute` and
+//! `health_check` symbols — so it stays stable across Rust compiler versions
+//! and across modules built against a different SDK revision than the host:
+//!
+//! ```c
+//! uint32_t secv_abi_version(void);
+//! char *secv_execute(const char *context_json);   // ExecutionContext -> ModuleResult
+//! int32_t secv_health_check(void);                // optional
+//! void secv_free_string(char *s);                 // frees secv_execute's return value
+//! ```
+//!
+//! This is a discrete-symbol ABI rather than a single
+//! `secv_plugin_register() -> *mut PluginVTable` entry point — simpler to
+//! bind from a shared library without hand-writing a vtable layout, at the
+//! cost that `validate_inputs`/`cleanup` never cross the FFI boundary:
+//! input validation stays on the Rust side against `module.json`'s declared
+//! `inputs`, and there is nothing for a native module to clean up since it
+//! owns no state beyond what `execute` allocates and frees per call.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::path::Path;
+
+use async_trait::async_trait;
+use libloading::{Library, Symbol};
+use tokio_util::sync::CancellationToken;
+
+use crate::{ExecutionContext, ModuleMetadata, ModuleResult, SecVError, SecVModule};
+
+/// Bumped whenever the shape of the exchanged JSON or the required symbol
+/// set changes. Modules built against a different version are refused at
+/// load time rather than failing unpredictably at call time.
+pub const SECV_ABI_VERSION: u32 = 1;
+
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+type ExecuteFn = unsafe extern "C" fn(*const c_char) -> *mut c_char;
+type HealthCheckFn = unsafe extern "C" fn() -> i32;
+type FreeStringFn = unsafe extern "C" fn(*mut c_char);
+
+/// A module backed by a dynamically loaded shared library.
+///
+/// Declared metadata (name/inputs/capabilities/...) still comes from
+/// `module.json`, same as `PlaceholderModule` — only `execute` and
+/// `health_check` cross the FFI boundary.
+pub struct NativeModule {
+    metadata: ModuleMetadata,
+    // Keeps the mapped shared object alive for as long as the function
+    // pointers below remain callable.
+    _library: Library,
+    execute_fn: ExecuteFn,
+    health_check_fn: Option<HealthCheckFn>,
+    free_string_fn: FreeStringFn,
+}
+
+// The extracted function pointers are plain data once copied out of the
+// `Symbol`s they came from, and the shared library underneath them never
+// mutates; safe to hand across threads as long as `_library` outlives them.
+unsafe impl Send for NativeModule {}
+unsafe impl Sync for NativeModule {}
+
+impl NativeModule {
+    /// Loads `entry_point` (resolved relative to `module_dir`) and verifies
+    /// it negotiates the same ABI version this host speaks.
+    ///
+    /// # Safety
+    /// Loading and calling into an arbitrary shared library is inherently
+    /// unsafe: the library is trusted to honor the ABI documented above.
+    pub unsafe fn load(
+        module_dir: &Path,
+        entry_point: &str,
+        metadata: ModuleMetadata,
+    ) -> Result<Self, SecVError> {
+        let library_path = module_dir.join(entry_point);
+        let library = Library::new(&library_path).map_err(|e| {
+            SecVError::ExecutionFailed(format!(
+                "Failed to load native module '{}': {}",
+                metadata.name, e
+            ))
+        })?;
+
+        let abi_version: Symbol<AbiVersionFn> =
+            library.get(b"secv_abi_version\0").map_err(|e| {
+                SecVError::ValidationFailed(format!(
+                    "'{}' is missing secv_abi_version: {}",
+                    metadata.name, e
+                ))
+            })?;
+        let reported = abi_version();
+        if reported != SECV_ABI_VERSION {
+            return Err(SecVError::ValidationFailed(format!(
+                "'{}' was built against plugin ABI v{}, host speaks v{}",
+                metadata.name, reported, SECV_ABI_VERSION
+            )));
+        }
+
+        let execute_fn: Symbol<ExecuteFn> = library.get(b"secv_execute\0").map_err(|e| {
+            SecVError::ValidationFailed(format!(
+                "'{}' is missing secv_execute: {}",
+                metadata.name, e
+            ))
+        })?;
+        let free_string_fn: Symbol<FreeStringFn> =
+            library.get(b"secv_free_string\0").map_err(|e| {
+                SecVError::ValidationFailed(format!(
+                    "'{}' is missing secv_free_string: {}",
+                    metadata.name, e
+                ))
+            })?;
+        // Optional: modules that have nothing cheaper to check than a full
+        // dependency scan can skip exporting this symbol.
+        let health_check_fn: Option<Symbol<HealthCheckFn>> =
+            library.get(b"secv_health_check\0").ok();
+
+        Ok(Self {
+            metadata,
+            execute_fn: *execute_fn,
+            health_check_fn: health_check_fn.map(|symbol| *symbol),
+            free_string_fn: *free_string_fn,
+            _library: library,
+        })
+    }
+}
+
+#[async_trait]
+impl SecVModule for NativeModule {
+    fn metadata(&self) -> &ModuleMetadata {
+        &self.metadata
+    }
+
+    async fn validate_dependencies(&self) -> Result<(), SecVError> {
+        for dep in &self.metadata.dependencies {
+            match tokio::process::Command::new("which").arg(dep).output().await {
+                Ok(output) if output.status.success() => continue,
+                _ => return Err(SecVError::DependencyMissing(dep.clone())),
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_inputs(
+        &self,
+        inputs: &std::collections::HashMap<String, serde_json::Value>,
+    ) -> Result<(), SecVError> {
+        for (key, spec) in &self.metadata.inputs {
+            if spec.required && !inputs.contains_key(key) {
+                return Err(SecVError::ValidationFailed(format!(
+                    "Required input '{}' is missing",
+                    key
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    async fn execute(
+        &self,
+        context: ExecutionContext,
+        cancel: CancellationToken,
+    ) -> Result<ModuleResult, SecVError> {
+        let input = CString::new(serde_json::to_string(&context)?)
+            .map_err(|e| SecVError::ExecutionFailed(format!("Context has embedded NUL: {}", e)))?;
+        let execute_fn = self.execute_fn;
+        let free_string_fn = self.free_string_fn;
+
+        // The FFI call itself can't be interrupted mid-flight, so it runs on
+        // a blocking thread and `cancel` only races its *completion* — the
+        // same cooperative-cancellation contract every `SecVModule` honors.
+        let call = tokio::task::spawn_blocking(move || unsafe {
+            let out_ptr = execute_fn(input.as_ptr());
+            if out_ptr.is_null() {
+                return Err(SecVError::ExecutionFailed(
+                    "Native module returned a null result".to_string(),
+                ));
+            }
+            let out = CStr::from_ptr(out_ptr).to_string_lossy().into_owned();
+            free_string_fn(out_ptr);
+            Ok(out)
+        });
+
+        let output = tokio::select! {
+            joined = call => joined.map_err(|e| {
+                SecVError::ExecutionFailed(format!("Native module panicked: {}", e))
+            })??,
+            _ = cancel.cancelled() => {
+                return Err(SecVError::ExecutionFailed("Execution cancelled".to_string()));
+            }
+        };
+
+        serde_json::from_str(&output).map_err(SecVError::from)
+    }
+
+    async fn health_check(&self) -> Result<bool, SecVError> {
+        match self.health_check_fn {
+            Some(health_check_fn) => Ok(unsafe { health_check_fn() } != 0),
+            None => self.validate_dependencies().await.map(|_| true),
+        }
+    }
+}
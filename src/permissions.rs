@@ -0,0 +1,186 @@
+//! Capability-based permission sandbox.
+//!
+//! `ModuleMetadata::capabilities` and `risk_level` used to be purely
+//! decorative. This module turns them into a real safety boundary: before a
+//! module runs, its declared capabilities are checked against an allow-list
+//! built from CLI flags and/or a policy file, with an interactive fallback
+//! for anything not explicitly decided up front.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use colored::*;
+use dialoguer::Confirm;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::{ModuleMetadata, RiskLevel, SecVError};
+
+/// A policy file (`secv-policy.json`/`.toml` equivalent, loaded as JSON here
+/// to match the rest of the codebase's serde_json-first conventions).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PolicyFile {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+/// Bundled modules declare human-readable capability tags (`port-scanning`,
+/// `os-fingerprinting`, `network-scanning`, ...) rather than the coarser
+/// vocabulary the CLI's `--allow-*`/`--deny-*` flags and policy files speak
+/// (`net`/`fs-read`/`fs-write`/`process-spawn`/`raw-socket`). Mapping each
+/// known tag onto the flag category it actually needs lets `--allow-net` (or
+/// an equivalent policy-file entry) authorize the project's own modules
+/// instead of only ever matching a flag name verbatim. Unrecognized tags pass
+/// through unchanged, so a module author can still declare (and a policy can
+/// still target) one of the flag names directly.
+fn normalize_capability(capability: &str) -> &str {
+    match capability {
+        "port-scanning" | "service-detection" | "os-fingerprinting"
+        | "network-scanning" | "port-detection" => "net",
+        _ => capability,
+    }
+}
+
+/// Runtime permission decisions: an allow-list and deny-list of capability
+/// names, plus grants the user has approved interactively for this session.
+pub struct PermissionPolicy {
+    allow: HashSet<String>,
+    deny: HashSet<String>,
+    /// When false, unresolved capabilities are hard-denied instead of prompted.
+    interactive: bool,
+    session_grants: Mutex<HashSet<String>>,
+}
+
+impl PermissionPolicy {
+    pub fn new(allow: Vec<String>, deny: Vec<String>, interactive: bool) -> Self {
+        Self {
+            allow: allow.into_iter().collect(),
+            deny: deny.into_iter().collect(),
+            interactive,
+            session_grants: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub async fn from_file(path: &Path, interactive: bool) -> Result<Self, SecVError> {
+        let content = tokio::fs::read_to_string(path).await?;
+        let file: PolicyFile = serde_json::from_str(&content)?;
+        Ok(Self::new(file.allow, file.deny, interactive))
+    }
+
+    /// Merges additional allow/deny entries (e.g. from CLI flags) on top of
+    /// whatever this policy was already constructed with.
+    pub fn extend(&mut self, allow: Vec<String>, deny: Vec<String>) {
+        self.allow.extend(allow);
+        self.deny.extend(deny);
+    }
+
+    /// Returns a copy of this policy with interactive prompting forced off,
+    /// keeping the same allow/deny lists but starting with no session
+    /// grants. Used for contexts with no TTY to prompt on — `secv serve`,
+    /// `secv serve-agents`, and `secv daemon` — where reaching `.interact()`
+    /// would otherwise turn every unresolved capability check into a
+    /// "Prompt failed" `PermissionDenied` instead of a clear 403.
+    pub fn non_interactive(&self) -> Self {
+        Self {
+            allow: self.allow.clone(),
+            deny: self.deny.clone(),
+            interactive: false,
+            session_grants: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Checks a module's declared capabilities (and risk level) against this
+    /// policy, hard-failing or prompting as needed. Returns
+    /// `SecVError::PermissionDenied` if the module may not run.
+    pub async fn check(&self, metadata: &ModuleMetadata) -> Result<(), SecVError> {
+        for capability in &metadata.capabilities {
+            let flag = normalize_capability(capability);
+
+            // Match both the normalized flag and the raw tag, so a policy
+            // file written against the specific capability name (as they
+            // all were before normalization existed) keeps working exactly
+            // as before, alongside newly-supported flag-level entries.
+            if self.deny.contains(flag) || self.deny.contains(capability.as_str()) {
+                return Err(SecVError::PermissionDenied(format!(
+                    "Capability '{}' is explicitly denied for module '{}'",
+                    capability, metadata.name
+                )));
+            }
+
+            let already_granted = self.allow.contains(flag)
+                || self.allow.contains(capability.as_str())
+                || self.session_grants.lock().await.contains(flag)
+                || self.session_grants.lock().await.contains(capability.as_str());
+
+            if !already_granted {
+                if !self.interactive {
+                    return Err(SecVError::PermissionDenied(format!(
+                        "Module '{}' wants capability '{}', which is not allow-listed \
+                         (run with --allow-{} or a policy file, or drop --no-prompt)",
+                        metadata.name, capability, flag
+                    )));
+                }
+
+                let prompt = format!(
+                    "Module '{}' wants capability '{}' — allow?",
+                    metadata.name, capability
+                );
+                let granted = Confirm::new()
+                    .with_prompt(prompt)
+                    .default(false)
+                    .interact()
+                    .map_err(|e| SecVError::PermissionDenied(format!("Prompt failed: {}", e)))?;
+
+                if !granted {
+                    return Err(SecVError::PermissionDenied(format!(
+                        "User declined capability '{}' for module '{}'",
+                        capability, metadata.name
+                    )));
+                }
+
+                self.session_grants.lock().await.insert(flag.to_string());
+            }
+        }
+
+        // High/Critical risk modules require explicit confirmation even when
+        // every individual capability was already granted.
+        if matches!(metadata.risk_level, RiskLevel::High | RiskLevel::Critical) {
+            let grant_key = format!("risk:{}", metadata.name);
+            let already_confirmed = self.session_grants.lock().await.contains(&grant_key);
+
+            if !already_confirmed {
+                if !self.interactive {
+                    return Err(SecVError::PermissionDenied(format!(
+                        "Module '{}' has risk level {:?} and requires interactive confirmation",
+                        metadata.name, metadata.risk_level
+                    )));
+                }
+
+                println!(
+                    "{} {} {:?}",
+                    "⚠️ ".yellow(),
+                    format!("Module '{}' is risk level", metadata.name).yellow().bold(),
+                    metadata.risk_level
+                );
+                let granted = Confirm::new()
+                    .with_prompt("Proceed anyway?")
+                    .default(false)
+                    .interact()
+                    .map_err(|e| SecVError::PermissionDenied(format!("Prompt failed: {}", e)))?;
+
+                if !granted {
+                    return Err(SecVError::PermissionDenied(format!(
+                        "User declined to run {:?}-risk module '{}'",
+                        metadata.risk_level, metadata.name
+                    )));
+                }
+
+                self.session_grants.lock().await.insert(grant_key);
+            }
+        }
+
+        Ok(())
+    }
+}
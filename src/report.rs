@@ -0,0 +1,158 @@
+//! Machine-readable report export for CI pipelines.
+//!
+//! Module and workflow results are collected into a plain
+//! `HashMap<String, ModuleResult>` regardless of how they were produced;
+//! this module turns that into formats CI dashboards already understand
+//! (JUnit XML, SARIF) instead of only printing colored text to a terminal a
+//! pipeline never sees. New formats (JSON Lines, HTML, ...) are added by
+//! implementing `ResultReporter` and registering it in `reporter_for`.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::{ModuleResult, SecVError};
+
+/// Renders a completed result set into a specific report format.
+pub trait ResultReporter {
+    /// Serializes `results` (keyed by workflow step or module name) into
+    /// this reporter's format. `report_name` labels the run as a whole
+    /// (the workflow name, or the module name for a single `Execute`).
+    fn render(&self, report_name: &str, results: &HashMap<String, ModuleResult>) -> Result<String, SecVError>;
+}
+
+/// JUnit XML: one `<testcase>` per step, `errors` as `<failure>`, `warnings`
+/// as `<system-out>`, `artifacts` as `<properties>`. The format most
+/// GitLab/GitHub pipelines already render in a "Tests" tab.
+pub struct JUnitReporter;
+
+impl ResultReporter for JUnitReporter {
+    fn render(&self, report_name: &str, results: &HashMap<String, ModuleResult>) -> Result<String, SecVError> {
+        let total = results.len();
+        let failures = results.values().filter(|result| !result.success).count();
+        let total_time_secs = results.values().map(|result| result.execution_time_ms).sum::<u64>() as f64 / 1000.0;
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+            escape_xml(report_name), total, failures, total_time_secs
+        ));
+
+        for (name, result) in results {
+            xml.push_str(&format!(
+                "  <testcase name=\"{}\" time=\"{:.3}\">\n",
+                escape_xml(name),
+                result.execution_time_ms as f64 / 1000.0
+            ));
+
+            for error in &result.errors {
+                xml.push_str(&format!(
+                    "    <failure message=\"{}\"></failure>\n",
+                    escape_xml(error)
+                ));
+            }
+
+            if !result.warnings.is_empty() {
+                xml.push_str(&format!(
+                    "    <system-out>{}</system-out>\n",
+                    escape_xml(&result.warnings.join("\n"))
+                ));
+            }
+
+            if !result.artifacts.is_empty() {
+                xml.push_str("    <properties>\n");
+                for artifact in &result.artifacts {
+                    xml.push_str(&format!(
+                        "      <property name=\"artifact\" value=\"{}\"></property>\n",
+                        escape_xml(artifact)
+                    ));
+                }
+                xml.push_str("    </properties>\n");
+            }
+
+            xml.push_str("  </testcase>\n");
+        }
+
+        xml.push_str("</testsuite>\n");
+        Ok(xml)
+    }
+}
+
+/// SARIF 2.1.0: each error/warning becomes a `result` entry, so findings
+/// show up as annotations in GitHub's code-scanning UI.
+pub struct SarifReporter;
+
+impl ResultReporter for SarifReporter {
+    fn render(&self, report_name: &str, results: &HashMap<String, ModuleResult>) -> Result<String, SecVError> {
+        let mut sarif_results = Vec::new();
+        for (name, result) in results {
+            for error in &result.errors {
+                sarif_results.push(serde_json::json!({
+                    "ruleId": name,
+                    "level": "error",
+                    "message": { "text": error },
+                }));
+            }
+            for warning in &result.warnings {
+                sarif_results.push(serde_json::json!({
+                    "ruleId": name,
+                    "level": "warning",
+                    "message": { "text": warning },
+                }));
+            }
+        }
+
+        let sarif = serde_json::json!({
+            "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "secv",
+                        "informationUri": "https://github.com/secvulnhub/SecV",
+                    }
+                },
+                "properties": { "reportName": report_name },
+                "results": sarif_results,
+            }]
+        });
+
+        serde_json::to_string_pretty(&sarif).map_err(SecVError::from)
+    }
+}
+
+fn escape_xml(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Resolves a `--report` format name (case-insensitive) to its reporter.
+pub fn reporter_for(format: &str) -> Option<Box<dyn ResultReporter>> {
+    match format.to_lowercase().as_str() {
+        "junit" => Some(Box::new(JUnitReporter)),
+        "sarif" => Some(Box::new(SarifReporter)),
+        _ => None,
+    }
+}
+
+/// Renders `results` with the reporter for `format` and writes it to `path`.
+pub async fn write_report(
+    format: &str,
+    report_name: &str,
+    results: &HashMap<String, ModuleResult>,
+    path: &Path,
+) -> Result<(), SecVError> {
+    let reporter = reporter_for(format).ok_or_else(|| {
+        SecVError::ValidationFailed(format!(
+            "Unknown report format '{}' (expected 'junit' or 'sarif')",
+            format
+        ))
+    })?;
+    let rendered = reporter.render(report_name, results)?;
+    tokio::fs::write(path, rendered)
+        .await
+        .map_err(SecVError::from)
+}
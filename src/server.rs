@@ -0,0 +1,293 @@
+//! HTTP REST control-plane server (`secv serve`).
+//!
+//! Exposes the same operations the local CLI already offers —
+//! `ModuleLoader::modules_by_category`, `get_module`, module execution, and
+//! workflow execution — as a small JSON API, so an orchestrator or CI job
+//! can drive SecV over the network instead of shelling out. Kept on a plain
+//! `TcpListener` with a hand-rolled HTTP/1.1 request/response, the same
+//! style `agent.rs` uses for its TCP transport, rather than pulling in a
+//! full web framework for four routes.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use colored::*;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::agent::AgentServer;
+use crate::permissions::PermissionPolicy;
+use crate::{
+    ExecutionContext, ModuleLoader, ModuleMetadata, ModuleResult, SecVError, WorkflowDefinition,
+    WorkflowEngine,
+};
+use tokio_util::sync::CancellationToken;
+
+/// Shared state handed to every connection.
+struct ServerState {
+    module_loader: Arc<ModuleLoader>,
+    permission_policy: Arc<PermissionPolicy>,
+    workflow_engine: WorkflowEngine,
+    /// When set, requests must carry a matching `Authorization: Bearer <token>` header.
+    token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExecuteRequest {
+    module: String,
+    target: String,
+    #[serde(default)]
+    parameters: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkflowRequest {
+    /// Either an inline workflow definition...
+    #[serde(default)]
+    workflow: Option<WorkflowDefinition>,
+    /// ...or a path to one already on disk. `workflow` wins if both are set.
+    #[serde(default)]
+    path: Option<String>,
+    target: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ModuleInfoResponse {
+    metadata: ModuleMetadata,
+    healthy: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// Starts the control-plane server and blocks forever, accepting one task
+/// per connection. When `agent_server` is set, `POST /workflow` steps whose
+/// module isn't loaded locally are dispatched to whichever remote agent has
+/// registered with it, instead of failing with 404.
+pub async fn serve(
+    bind: &str,
+    token: Option<String>,
+    module_loader: Arc<ModuleLoader>,
+    permission_policy: Arc<PermissionPolicy>,
+    agent_server: Option<Arc<AgentServer>>,
+) -> Result<(), SecVError> {
+    let mut workflow_engine = WorkflowEngine::new(module_loader.clone())
+        .with_permission_policy(permission_policy.clone());
+    if let Some(agent_server) = agent_server {
+        workflow_engine = workflow_engine.with_agent_server(agent_server);
+    }
+    let state = Arc::new(ServerState {
+        module_loader,
+        permission_policy,
+        workflow_engine,
+        token,
+    });
+
+    let listener = TcpListener::bind(bind).await?;
+    println!(
+        "{}",
+        format!("🌐 Control plane listening on {} (GET /modules, GET /modules/{{name}}, POST /execute, POST /workflow)", bind)
+            .green()
+            .bold()
+    );
+    if state.token.is_none() {
+        println!("{}", "⚠️  No --token set: the API is unauthenticated".yellow().bold());
+    }
+
+    loop {
+        let (stream, addr) = listener.accept().await?;
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, state).await {
+                eprintln!("{}", format!("Connection from {} failed: {}", addr, e).red());
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: TcpStream, state: Arc<ServerState>) -> Result<(), SecVError> {
+    let mut reader = BufReader::new(stream);
+    let request = read_request(&mut reader).await?;
+
+    if let Some(expected) = &state.token {
+        let provided = request
+            .headers
+            .get("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if provided != Some(expected.as_str()) {
+            let body = serde_json::to_vec(&ErrorResponse { error: "Unauthorized".to_string() })?;
+            return write_response(reader.get_mut(), 401, &body).await;
+        }
+    }
+
+    let (status, body) = match route(&request, &state).await {
+        Ok((status, body)) => (status, body),
+        Err(err) => {
+            let status = status_for_error(&err);
+            let body = serde_json::to_vec(&ErrorResponse { error: err.to_string() })?;
+            (status, body)
+        }
+    };
+
+    write_response(reader.get_mut(), status, &body).await
+}
+
+async fn route(request: &HttpRequest, state: &ServerState) -> Result<(u16, Vec<u8>), SecVError> {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/modules") => {
+            let categories = state.module_loader.modules_by_category();
+            let metadata: HashMap<String, Vec<ModuleMetadata>> = categories
+                .into_iter()
+                .map(|(category, modules)| {
+                    (category, modules.iter().map(|m| m.metadata().clone()).collect())
+                })
+                .collect();
+            Ok((200, serde_json::to_vec(&metadata)?))
+        }
+
+        ("GET", path) if path.starts_with("/modules/") => {
+            let name = &path["/modules/".len()..];
+            let module = state
+                .module_loader
+                .get_module(name)
+                .ok_or_else(|| state.module_loader.not_found_error(name))?;
+            let healthy = module.health_check().await.unwrap_or(false);
+            let response = ModuleInfoResponse { metadata: module.metadata().clone(), healthy };
+            Ok((200, serde_json::to_vec(&response)?))
+        }
+
+        ("POST", "/execute") => {
+            let req: ExecuteRequest = serde_json::from_slice(&request.body)?;
+            let module = state
+                .module_loader
+                .get_module(&req.module)
+                .ok_or_else(|| state.module_loader.not_found_error(&req.module))?;
+
+            state.permission_policy.check(module.metadata()).await?;
+            module.validate_inputs(&req.parameters)?;
+
+            let context = ExecutionContext {
+                target: req.target,
+                parameters: req.parameters,
+                results: HashMap::new(),
+                metadata: HashMap::new(),
+            };
+            let result: ModuleResult = module.execute(context, CancellationToken::new()).await?;
+            Ok((200, serde_json::to_vec(&result)?))
+        }
+
+        ("POST", "/workflow") => {
+            let req: WorkflowRequest = serde_json::from_slice(&request.body)?;
+            let workflow = match req.workflow {
+                Some(workflow) => workflow,
+                None => {
+                    let path = req.path.ok_or_else(|| {
+                        SecVError::ValidationFailed("Request must set 'workflow' or 'path'".to_string())
+                    })?;
+                    state.workflow_engine.load_workflow(std::path::Path::new(&path)).await
+                        .map_err(|e| SecVError::ValidationFailed(e.to_string()))?
+                }
+            };
+
+            let results = state
+                .workflow_engine
+                .execute_workflow(workflow, req.target, CancellationToken::new(), None, crate::ShuffleMode::Off)
+                .await
+                .map_err(|e| match e.downcast::<SecVError>() {
+                    Ok(secv_error) => secv_error,
+                    Err(other) => SecVError::WorkflowError(other.to_string()),
+                })?;
+            Ok((200, serde_json::to_vec(&results)?))
+        }
+
+        _ => Ok((404, serde_json::to_vec(&ErrorResponse { error: "No such route".to_string() })?)),
+    }
+}
+
+/// Maps a `SecVError` to the HTTP status code it represents.
+fn status_for_error(err: &SecVError) -> u16 {
+    match err {
+        SecVError::ModuleNotFound(_) => 404,
+        SecVError::ValidationFailed(_) => 400,
+        SecVError::PermissionDenied(_) => 403,
+        SecVError::DependencyMissing(_) => 412,
+        SecVError::ExecutionFailed(_) => 500,
+        SecVError::WorkflowError(_) => 500,
+        SecVError::IoError(_) => 500,
+        SecVError::SerializationError(_) => 400,
+    }
+}
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// Reads a request line, headers, and (if `Content-Length` is set) a body
+/// off `reader`. Deliberately minimal: no chunked transfer encoding, no
+/// keep-alive — every connection is closed after one response.
+async fn read_request(reader: &mut BufReader<TcpStream>) -> Result<HttpRequest, SecVError> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    if request_line.trim().is_empty() {
+        return Err(SecVError::ExecutionFailed("Empty request".to_string()));
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let path = parts.next().unwrap_or("/").to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).await?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            headers.insert(key.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let content_length: usize = headers
+        .get("content-length")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0u8; content_length];
+    if content_length > 0 {
+        reader.read_exact(&mut body).await?;
+    }
+
+    Ok(HttpRequest { method, path, headers, body })
+}
+
+fn status_reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        412 => "Precondition Failed",
+        _ => "Internal Server Error",
+    }
+}
+
+async fn write_response(stream: &mut TcpStream, status: u16, body: &[u8]) -> Result<(), SecVError> {
+    let header = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        status_reason(status),
+        body.len()
+    );
+    stream.write_all(header.as_bytes()).await?;
+    stream.write_all(body).await?;
+    stream.flush().await?;
+    Ok(())
+}
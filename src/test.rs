@@ -0,0 +1,180 @@
+//! Declarative module test harness (`secv test`).
+//!
+//! A module directory may carry `*.case.json` fixtures alongside its
+//! `module.json`: inputs to execute the module with, and the shape of the
+//! `ModuleResult` expected back. No human and no real target is in the
+//! loop — each case is just `execute()` plus a handful of assertions, so a
+//! module author gets a standard way to ship regression tests instead of
+//! inventing one per module.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use colored::*;
+use regex::Regex;
+use serde::Deserialize;
+use tokio_util::sync::CancellationToken;
+
+use crate::{ExecutionContext, SecVError, SecVModule};
+
+/// One `*.case.json` fixture: inputs to run the module with, and what the
+/// resulting `ModuleResult` must look like.
+#[derive(Debug, Deserialize)]
+struct TestCase {
+    #[serde(default)]
+    inputs: HashMap<String, serde_json::Value>,
+    expect: Expectation,
+}
+
+#[derive(Debug, Deserialize)]
+struct Expectation {
+    #[serde(default = "default_success")]
+    success: bool,
+    /// Dot-path into `ModuleResult.data` -> a regex the stringified value
+    /// at that path must match. Users supply real regex; no escaping is
+    /// done on their behalf.
+    #[serde(default)]
+    data: HashMap<String, String>,
+    #[serde(default)]
+    warnings_contain: Vec<String>,
+}
+
+fn default_success() -> bool {
+    true
+}
+
+/// Outcome of running one fixture.
+pub struct CaseResult {
+    pub name: String,
+    pub passed: bool,
+    pub failures: Vec<String>,
+}
+
+/// Runs every `*.case.json` fixture found directly in `module_dir` against
+/// `module`, in filename order. A fixture that fails to parse counts as a
+/// failed case rather than aborting the rest of the run.
+pub async fn run_cases(
+    module: &Arc<dyn SecVModule>,
+    module_dir: &Path,
+) -> Result<Vec<CaseResult>, SecVError> {
+    let mut case_paths = Vec::new();
+    let mut entries = tokio::fs::read_dir(module_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let is_case = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(|stem| stem.ends_with(".case"))
+            .unwrap_or(false);
+        if is_case && path.extension().map(|ext| ext == "json").unwrap_or(false) {
+            case_paths.push(path);
+        }
+    }
+    case_paths.sort();
+
+    let mut results = Vec::with_capacity(case_paths.len());
+    for path in case_paths {
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("case")
+            .to_string();
+
+        let case = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => serde_json::from_str::<TestCase>(&content)
+                .map_err(|e| format!("invalid fixture: {}", e)),
+            Err(e) => Err(format!("failed to read fixture: {}", e)),
+        };
+
+        results.push(match case {
+            Ok(case) => run_case(module, name, case).await,
+            Err(failure) => CaseResult { name, passed: false, failures: vec![failure] },
+        });
+    }
+
+    Ok(results)
+}
+
+async fn run_case(module: &Arc<dyn SecVModule>, name: String, case: TestCase) -> CaseResult {
+    let context = ExecutionContext {
+        target: "secv-test".to_string(),
+        parameters: case.inputs,
+        results: HashMap::new(),
+        metadata: HashMap::new(),
+    };
+
+    let mut failures = Vec::new();
+    match module.execute(context, CancellationToken::new()).await {
+        Ok(result) => {
+            if result.success != case.expect.success {
+                failures.push(format!(
+                    "expected success={}, got {}",
+                    case.expect.success, result.success
+                ));
+            }
+
+            for (path, pattern) in &case.expect.data {
+                match resolve_data_path(&result.data, path) {
+                    Some(value) => match Regex::new(pattern) {
+                        Ok(re) => {
+                            let text = stringify(&value);
+                            if !re.is_match(&text) {
+                                failures.push(format!(
+                                    "data.{} = '{}' does not match /{}/",
+                                    path, text, pattern
+                                ));
+                            }
+                        }
+                        Err(e) => failures.push(format!("bad regex for data.{}: {}", path, e)),
+                    },
+                    None => failures.push(format!("data.{} not found in result", path)),
+                }
+            }
+
+            for expected in &case.expect.warnings_contain {
+                if !result.warnings.iter().any(|warning| warning.contains(expected.as_str())) {
+                    failures.push(format!("no warning contains '{}'", expected));
+                }
+            }
+        }
+        Err(e) => failures.push(format!("execute() returned an error: {}", e)),
+    }
+
+    CaseResult { name, passed: failures.is_empty(), failures }
+}
+
+/// Resolves a dot-notation path into a `ModuleResult.data` value, the same
+/// notation `WorkflowEngine::resolve_context_path` uses for `results.*`.
+fn resolve_data_path(data: &serde_json::Value, path: &str) -> Option<serde_json::Value> {
+    let mut current = data;
+    for part in path.split('.') {
+        current = current.get(part)?;
+    }
+    Some(current.clone())
+}
+
+fn stringify(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Prints a pass/fail line per case (and each failure's reason), returning
+/// whether every case in `results` passed.
+pub fn print_summary(module_name: &str, results: &[CaseResult]) -> bool {
+    let mut all_passed = true;
+    for result in results {
+        if result.passed {
+            println!("  {} {}::{}", "✅".green(), module_name.cyan(), result.name);
+        } else {
+            all_passed = false;
+            println!("  {} {}::{}", "❌".red(), module_name.cyan(), result.name);
+            for failure in &result.failures {
+                println!("      {} {}", "-".dimmed(), failure.red());
+            }
+        }
+    }
+    all_passed
+}
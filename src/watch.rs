@@ -0,0 +1,92 @@
+//! Debounced file-watching support for `--watch` mode.
+//!
+//! Callers pass whichever paths are relevant to their run (a module's
+//! `module.json`, or a workflow file plus its loaded steps' `module.json`s);
+//! this module just coalesces bursts of filesystem events across all of them
+//! (editors tend to write a file in several small writes) into a single
+//! "something changed" signal roughly every 200ms.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use colored::*;
+use notify::{RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+
+use crate::{ModuleResult, SecVError};
+
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Blocks until one of `paths` changes on disk, coalescing rapid-fire events.
+/// Paths that don't exist yet (e.g. a target list created later) are skipped.
+pub async fn wait_for_change(paths: &[PathBuf]) -> Result<(), SecVError> {
+    let (tx, mut rx) = mpsc::channel(16);
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if res.is_ok() {
+            let _ = tx.blocking_send(());
+        }
+    })
+    .map_err(|e| SecVError::ExecutionFailed(format!("Failed to start file watcher: {}", e)))?;
+
+    for path in paths {
+        if path.exists() {
+            watcher
+                .watch(path, RecursiveMode::NonRecursive)
+                .map_err(|e| SecVError::ExecutionFailed(format!("Failed to watch {:?}: {}", path, e)))?;
+        }
+    }
+
+    // Wait for the first event, then drain anything else that arrives within
+    // the debounce window so a flurry of writes only triggers one re-run.
+    if rx.recv().await.is_none() {
+        return Err(SecVError::ExecutionFailed("File watcher channel closed".to_string()));
+    }
+    loop {
+        tokio::select! {
+            _ = rx.recv() => continue,
+            _ = tokio::time::sleep(DEBOUNCE) => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints a concise diff of which steps changed success/failure between runs.
+pub fn print_diff_summary(
+    previous: &std::collections::HashMap<String, ModuleResult>,
+    current: &std::collections::HashMap<String, ModuleResult>,
+) {
+    println!("\n{}", "--- Watch: changes since last run ---".magenta().bold());
+    let mut any_change = false;
+
+    for (module_name, result) in current {
+        match previous.get(module_name) {
+            Some(prev) if prev.success != result.success => {
+                any_change = true;
+                let transition = if result.success {
+                    "❌ → ✅".green()
+                } else {
+                    "✅ → ❌".red()
+                };
+                println!("  {}: {}", module_name.cyan().bold(), transition);
+            }
+            None => {
+                any_change = true;
+                println!("  {}: {}", module_name.cyan().bold(), "(new)".yellow());
+            }
+            _ => {}
+        }
+    }
+
+    for module_name in previous.keys() {
+        if !current.contains_key(module_name) {
+            any_change = true;
+            println!("  {}: {}", module_name.cyan().bold(), "(removed)".dimmed());
+        }
+    }
+
+    if !any_change {
+        println!("  {}", "No change in step outcomes.".dimmed());
+    }
+}
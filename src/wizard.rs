@@ -0,0 +1,224 @@
+//! Interactive scaffolding wizard (`secv new`, and offered at the end of
+//! `secv --init`).
+//!
+//! Walks a contributor through building a well-formed `module.json` or a
+//! multi-step workflow file using the same `dialoguer` prompts
+//! `InteractiveInterface` already uses elsewhere, instead of hand-writing
+//! the JSON from scratch and hoping it matches the schema.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use colored::*;
+use dialoguer::{Confirm, Input, Select};
+use regex::Regex;
+
+use crate::{
+    ErrorAction, InputSpec, ModuleLoader, ModuleMetadata, OutputSpec, RiskLevel, WorkflowDefinition,
+    WorkflowStep,
+};
+
+const RISK_LEVELS: [&str; 4] = ["Low", "Medium", "High", "Critical"];
+const INPUT_TYPES: [&str; 4] = ["string", "number", "boolean", "array"];
+const OUTPUT_TYPES: [&str; 4] = ["string", "number", "object", "array"];
+
+/// Prompts for a full `ModuleMetadata`, validates each answer along the
+/// way, and writes `tools/<category>/<name>/module.json`.
+pub async fn new_module() -> anyhow::Result<()> {
+    println!("\n{}", "--- New Module Wizard ---".blue().bold());
+
+    let name: String = Input::new().with_prompt("Module name (kebab-case)").interact_text()?;
+    let category: String = Input::new().with_prompt("Category (e.g. reconnaissance)").interact_text()?;
+    let description: String = Input::new().with_prompt("Description").interact_text()?;
+    let author: String = Input::new().with_prompt("Author").interact_text()?;
+    let version: String = Input::new().with_prompt("Version").default("1.0.0".to_string()).interact_text()?;
+
+    let risk_selection = Select::new().with_prompt("Risk level").items(&RISK_LEVELS).default(0).interact()?;
+    let risk_level = match risk_selection {
+        0 => RiskLevel::Low,
+        1 => RiskLevel::Medium,
+        2 => RiskLevel::High,
+        _ => RiskLevel::Critical,
+    };
+
+    let dependencies = prompt_string_list("Add an external command dependency (e.g. nmap)?")?;
+    let capabilities = prompt_string_list("Add a capability tag (e.g. network-scanning)?")?;
+    let inputs = prompt_inputs()?;
+    let outputs = prompt_outputs()?;
+
+    let entry_point: String = Input::new()
+        .with_prompt("Native entry point (shared library path; blank for a declarative/placeholder module)")
+        .allow_empty(true)
+        .interact_text()?;
+
+    let metadata = ModuleMetadata {
+        name: name.clone(),
+        version,
+        category: category.clone(),
+        description,
+        author,
+        dependencies,
+        inputs,
+        outputs,
+        capabilities,
+        risk_level,
+        entry_point: if entry_point.trim().is_empty() { None } else { Some(entry_point) },
+    };
+
+    let module_dir = PathBuf::from("tools").join(&category).join(&name);
+    tokio::fs::create_dir_all(&module_dir).await?;
+    let path = module_dir.join("module.json");
+    tokio::fs::write(&path, serde_json::to_string_pretty(&metadata)?).await?;
+
+    println!("{}", format!("✅ Wrote {}", path.display()).green().bold());
+    Ok(())
+}
+
+/// Assembles a multi-step workflow by selecting from already-discovered
+/// modules and wiring their `depends_on` edges, then writes it to
+/// `workflows/<name>.json`.
+pub async fn new_workflow(module_loader: &ModuleLoader) -> anyhow::Result<()> {
+    println!("\n{}", "--- New Workflow Wizard ---".blue().bold());
+
+    let module_names = module_loader.module_names();
+    if module_names.is_empty() {
+        println!("{}", "No modules discovered yet — nothing to wire into a workflow.".yellow());
+        return Ok(());
+    }
+
+    let name: String = Input::new().with_prompt("Workflow name").interact_text()?;
+    let description: String = Input::new().with_prompt("Description").interact_text()?;
+    let author: String = Input::new().with_prompt("Author").interact_text()?;
+
+    let mut steps: Vec<WorkflowStep> = Vec::new();
+    loop {
+        if !steps.is_empty() && !Confirm::new().with_prompt("Add another step?").default(false).interact()? {
+            break;
+        }
+
+        let module_selection = Select::new().with_prompt("Module for this step").items(&module_names).interact()?;
+        let module = module_names[module_selection].clone();
+
+        let step_name: String = Input::new()
+            .with_prompt("Step name")
+            .default(format!("{}-{}", module, steps.len() + 1))
+            .interact_text()?;
+
+        let mut depends_on = Vec::new();
+        if !steps.is_empty() {
+            loop {
+                if !Confirm::new().with_prompt("Add a dependency on an earlier step?").default(false).interact()? {
+                    break;
+                }
+                let step_names: Vec<&str> = steps.iter().map(|s| s.name.as_str()).collect();
+                let dep_selection = Select::new().with_prompt("Depends on").items(&step_names).interact()?;
+                depends_on.push(step_names[dep_selection].to_string());
+            }
+        }
+
+        steps.push(WorkflowStep {
+            name: step_name,
+            module,
+            inputs: HashMap::new(),
+            condition: None,
+            on_error: ErrorAction::Stop,
+            timeout_seconds: None,
+            depends_on,
+            retry_initial_delay_ms: 500,
+            retry_max_delay_ms: 30_000,
+            required_category: None,
+            required_capabilities: Vec::new(),
+        });
+    }
+
+    let workflow = WorkflowDefinition {
+        name: name.clone(),
+        description,
+        version: "1.0.0".to_string(),
+        author,
+        steps,
+        global_settings: HashMap::new(),
+    };
+
+    tokio::fs::create_dir_all("workflows").await?;
+    let path = PathBuf::from("workflows").join(format!("{}.json", name));
+    tokio::fs::write(&path, serde_json::to_string_pretty(&workflow)?).await?;
+
+    println!("{}", format!("✅ Wrote {}", path.display()).green().bold());
+    Ok(())
+}
+
+fn prompt_string_list(add_prompt: &str) -> anyhow::Result<Vec<String>> {
+    let mut items = Vec::new();
+    loop {
+        if !Confirm::new().with_prompt(add_prompt).default(false).interact()? {
+            break;
+        }
+        let value: String = Input::new().with_prompt("Value").interact_text()?;
+        items.push(value);
+    }
+    Ok(items)
+}
+
+fn prompt_inputs() -> anyhow::Result<HashMap<String, InputSpec>> {
+    let mut inputs = HashMap::new();
+    loop {
+        if !Confirm::new().with_prompt("Add an input?").default(inputs.is_empty()).interact()? {
+            break;
+        }
+
+        let key: String = Input::new().with_prompt("Input key").interact_text()?;
+        let description: String = Input::new().with_prompt("Description").interact_text()?;
+        let type_selection = Select::new().with_prompt("Type").items(&INPUT_TYPES).default(0).interact()?;
+        let required = Confirm::new().with_prompt("Required?").default(true).interact()?;
+
+        let default_value: String = Input::new()
+            .with_prompt("Default value (blank for none)")
+            .allow_empty(true)
+            .interact_text()?;
+
+        let validation_regex = loop {
+            let pattern: String = Input::new()
+                .with_prompt("Validation regex (blank for none)")
+                .allow_empty(true)
+                .interact_text()?;
+            if pattern.trim().is_empty() {
+                break None;
+            }
+            match Regex::new(&pattern) {
+                Ok(_) => break Some(pattern),
+                Err(e) => println!("{}", format!("  Invalid regex, try again: {}", e).red()),
+            }
+        };
+
+        inputs.insert(key, InputSpec {
+            description,
+            input_type: INPUT_TYPES[type_selection].to_string(),
+            required,
+            default_value: if default_value.trim().is_empty() { None } else { Some(default_value) },
+            validation_regex,
+        });
+    }
+    Ok(inputs)
+}
+
+fn prompt_outputs() -> anyhow::Result<HashMap<String, OutputSpec>> {
+    let mut outputs = HashMap::new();
+    loop {
+        if !Confirm::new().with_prompt("Add an output?").default(outputs.is_empty()).interact()? {
+            break;
+        }
+
+        let key: String = Input::new().with_prompt("Output key").interact_text()?;
+        let description: String = Input::new().with_prompt("Description").interact_text()?;
+        let type_selection = Select::new().with_prompt("Type").items(&OUTPUT_TYPES).default(0).interact()?;
+        let format: String = Input::new().with_prompt("Format (e.g. json, text)").default("json".to_string()).interact_text()?;
+
+        outputs.insert(key, OutputSpec {
+            description,
+            output_type: OUTPUT_TYPES[type_selection].to_string(),
+            format,
+        });
+    }
+    Ok(outputs)
+}